@@ -1 +1,43 @@
+// Note: Included by each test module, which uses only some of it.
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
 pub const TESTS_DATA_DIR: &str = "./tests-data/site-a";
+
+/// The minimal `ssite.toml` of the test sites (see `new_test_dir`).
+pub const TEST_SITE_CONFIG: &str = "[source]\ncontent_dir = \"content/\"\ndist_dir = \"_site\"\n";
+
+/// Create a new temp dir for the test (e.g., "gen-clean"), with the files (relative path and content,
+/// or a relative path ending with `/` for an empty dir). The eventual previous dir of the test is removed first.
+pub fn new_test_dir(name: &str, files: &[(&str, &str)]) -> std::io::Result<PathBuf> {
+	let dir = std::env::temp_dir().join(format!("ssite-tests-{name}-{}", std::process::id()));
+	let _ = fs::remove_dir_all(&dir);
+	fs::create_dir_all(&dir)?;
+
+	for (rel_path, content) in files {
+		let path = dir.join(rel_path);
+		if rel_path.ends_with('/') {
+			fs::create_dir_all(&path)?;
+		} else {
+			if let Some(parent) = path.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			fs::write(&path, content)?;
+		}
+	}
+
+	Ok(dir)
+}
+
+/// Create a new temp dir for the test with the site-a config, and its content dir (not copied),
+/// so that the site-a is generated into the test dir (not into the shared site-a dist dir).
+pub fn new_site_a_test_dir(name: &str) -> std::io::Result<PathBuf> {
+	let content_dir = Path::new(TESTS_DATA_DIR).join("content").canonicalize()?;
+	let config = fs::read_to_string(Path::new(TESTS_DATA_DIR).join("ssite.toml"))?.replace(
+		"content_dir = \"content/\"",
+		&format!("content_dir = {:?}", content_dir.display().to_string()),
+	);
+	new_test_dir(name, &[("ssite.toml", &config)])
+}
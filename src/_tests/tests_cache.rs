@@ -0,0 +1,86 @@
+use super::BuildCache;
+use crate::gen::cache::tests::_test_infra::new_test_dir;
+use anyhow::Result;
+use std::fs;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+#[test]
+fn cache_test_is_fresh() -> Result<()> {
+	let dir = new_test_dir("cache-fresh", &[])?;
+	let src = dir.join("page.md");
+	let frame = dir.join("_frame.html");
+	let dist = dir.join("page.html");
+	fs::write(&src, "# Page")?;
+	fs::write(&frame, "INCLUDE_CONTENT")?;
+	fs::write(&dist, "<h1>Page</h1>")?;
+	let deps = vec![frame.clone()];
+
+	let mut cache = BuildCache::default();
+	assert!(!cache.is_fresh(&src, &deps, &dist));
+
+	cache.update(&src, &deps, &dist);
+	assert!(cache.is_fresh(&src, &deps, &dist));
+
+	// different deps
+	assert!(!cache.is_fresh(&src, &[], &dist));
+
+	// changed frame (new cache to not reuse the hashes of this build)
+	let mut cache = BuildCache { entries: cache.entries, ..Default::default() };
+	fs::write(&frame, "changed INCLUDE_CONTENT")?;
+	assert!(!cache.is_fresh(&src, &deps, &dist));
+
+	// changed src
+	cache.update(&src, &deps, &dist);
+	fs::write(&src, "# Page changed")?;
+	assert!(!cache.is_fresh(&src, &deps, &dist));
+
+	// removed dist
+	cache.update(&src, &deps, &dist);
+	fs::remove_file(&dist)?;
+	assert!(!cache.is_fresh(&src, &deps, &dist));
+
+	fs::remove_dir_all(&dir)?;
+	Ok(())
+}
+
+#[test]
+fn cache_test_is_fresh_refreshes_stamp() -> Result<()> {
+	let dir = new_test_dir("cache-stamp", &[("page.md", "# Page"), ("page.html", "<h1>Page</h1>")])?;
+	let src = dir.join("page.md");
+	let dist = dir.join("page.html");
+
+	let mut cache = BuildCache::default();
+	cache.update(&src, &[], &dist);
+
+	// touched src (same content), so, fresh, with the new modified time recorded
+	let modified = SystemTime::now() - Duration::from_secs(3600);
+	fs::File::options().write(true).open(&src)?.set_modified(modified)?;
+	assert!(cache.is_fresh(&src, &[], &dist));
+	let entry = &cache.entries[&src];
+	assert_eq!(modified.duration_since(UNIX_EPOCH)?.as_nanos(), entry.modified);
+
+	fs::remove_dir_all(&dir)?;
+	Ok(())
+}
+
+#[test]
+fn cache_test_site_hash_change() -> Result<()> {
+	let dir = new_test_dir("cache-site-hash", &[])?;
+	let src = dir.join("image.png");
+	let dist = dir.join("dist-image.png");
+	fs::write(&src, "png")?;
+	fs::write(&dist, "png")?;
+
+	let mut cache = BuildCache { site_hash: "a".into(), ..Default::default() };
+	cache.update(&src, &[], &dist);
+	assert!(cache.is_fresh(&src, &[], &dist));
+
+	cache.site_hash = "b".into();
+	assert!(!cache.is_fresh(&src, &[], &dist));
+
+	fs::remove_dir_all(&dir)?;
+	Ok(())
+}
@@ -0,0 +1,110 @@
+use super::*;
+use crate::check::tests::_test_infra::new_site_a_test_dir;
+use crate::gen::gen;
+use anyhow::Result;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+#[test]
+fn check_test_resolve_link() -> Result<()> {
+	let resolve = |page_url: &str, link: &str| resolve_link(page_url, link);
+	let some = |path: &str, anchor: Option<&str>| Some((path.to_string(), anchor.map(|a| a.to_string())));
+
+	// relative and root relative
+	assert_eq!(some("/blog/hello", None), resolve("/blog/first-post", "hello"));
+	assert_eq!(some("/blog/hello", None), resolve("/blog/", "hello"));
+	assert_eq!(some("/hello", None), resolve("/blog/first-post", "../hello"));
+	assert_eq!(some("/", None), resolve("/blog/first-post", "../"));
+	assert_eq!(some("/sub-dir/", None), resolve("/blog/first-post", "/sub-dir/"));
+	assert_eq!(some("/my page", None), resolve("/", "my%20page?q=1"));
+
+	// anchors
+	assert_eq!(some("/hello", Some("intro")), resolve("/blog/first-post", "/hello#intro"));
+	assert_eq!(some("/blog/first-post", Some("intro")), resolve("/blog/first-post", "#intro"));
+	assert_eq!(some("/blog/first-post", None), resolve("/blog/first-post", "#"));
+
+	// external or empty
+	for link in ["https://example.com/a", "mailto:me@example.com", "//cdn.example.com/a.js", "data:image/png;base64,AA", ""] {
+		assert_eq!(None, resolve("/", link), "{link}");
+	}
+
+	Ok(())
+}
+
+#[test]
+fn check_test_target_file() -> Result<()> {
+	let dist_dir = Path::new("/site/_site");
+	let dist_files: HashSet<PathBuf> = ["hello", "sub-dir/index.html", "index.html", "assets/style.css"]
+		.iter()
+		.map(|f| dist_dir.join(f))
+		.collect();
+
+	assert_eq!(Some(dist_dir.join("hello")), target_file(dist_dir, &dist_files, "/hello"));
+	assert_eq!(Some(dist_dir.join("index.html")), target_file(dist_dir, &dist_files, "/"));
+	assert_eq!(Some(dist_dir.join("sub-dir/index.html")), target_file(dist_dir, &dist_files, "/sub-dir/"));
+	assert_eq!(Some(dist_dir.join("sub-dir/index.html")), target_file(dist_dir, &dist_files, "/sub-dir"));
+	assert_eq!(Some(dist_dir.join("assets/style.css")), target_file(dist_dir, &dist_files, "/assets/style.css"));
+	assert_eq!(None, target_file(dist_dir, &dist_files, "/hello.md"));
+	assert_eq!(None, target_file(dist_dir, &dist_files, "/hello.html"));
+
+	Ok(())
+}
+
+#[test]
+fn check_test_html_links_and_anchors() -> Result<()> {
+	let html = r#"<link href="/style.css"><a class="x" href='/a?x=1&amp;y=2'>A</a><img src="img.png">
+<h2 id="intro">Intro</h2><a name="old"></a><div data-id="not-an-anchor"></div>"#;
+
+	assert_eq!(vec!["/style.css", "/a?x=1&y=2", "img.png"], html_links(html));
+
+	let anchors = html_anchors(html);
+	assert!(anchors.contains("intro"));
+	assert!(anchors.contains("old"));
+	assert!(!anchors.contains("not-an-anchor"));
+
+	Ok(())
+}
+
+#[test]
+fn check_test_report_display() -> Result<()> {
+	let report = CheckReport {
+		files: 2,
+		links: 5,
+		broken: vec![
+			BrokenLink {
+				file: "content/hello.md".into(),
+				link: "other.md".into(),
+				cause: BrokenCause::MissingFile,
+			},
+			BrokenLink {
+				file: "content/hello.md".into(),
+				link: "/sub-dir/#nope".into(),
+				cause: BrokenCause::MissingAnchor,
+			},
+		],
+	};
+
+	let display = report.to_string();
+	assert!(display.contains("Broken links (2):\n  - content/hello.md\n      other.md (file not found)\n      /sub-dir/#nope (anchor not found)"));
+	assert!(display.ends_with("Check summary - files: 2, links: 5, broken: 2"));
+
+	Ok(())
+}
+
+#[test]
+fn check_test_generated_site() -> Result<()> {
+	let dir = new_site_a_test_dir("check-generated")?;
+	let site = Site::from_dir(&dir)?;
+	let report = tokio::runtime::Runtime::new()?.block_on(gen(&site, None))?;
+	assert!(!report.has_failures(), "{report}");
+
+	let report = check(&site)?;
+
+	assert!(report.files > 0 && report.links > 0);
+	assert!(!report.has_broken(), "{report}");
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
@@ -0,0 +1,137 @@
+use super::{include_lists, slugify, write_list_pages};
+use crate::gen::collections::tests::_test_infra::{new_test_dir, TEST_SITE_CONFIG};
+use crate::gen::{gen, BuildReport};
+use crate::site::{Collections, Site};
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+#[test]
+fn collections_test_load() -> Result<()> {
+	let site = Site::from_dir(Path::new("./tests-data/site-a"))?;
+
+	let collections = Collections::load(&site);
+	let blog = collections.get("blog").unwrap();
+
+	// Note: Most recent first, without the draft and the index page.
+	let urls: Vec<&str> = blog.items.iter().map(|i| i.url.as_str()).collect();
+	assert_eq!(vec!["/blog/third-post", "/blog/second-post", "/blog/first-post"], urls);
+	assert_eq!(vec!["rust", "web"], blog.items[2].tags);
+	assert_eq!(Some("/blog/"), blog.index_url.as_deref());
+
+	Ok(())
+}
+
+#[test]
+fn collections_test_include_lists() -> Result<()> {
+	let site = Site::from_dir(Path::new("./tests-data/site-a"))?;
+	site.set_collections(Collections::load(&site));
+
+	let html = include_lists(&site, "<main>\n<p>INCLUDE_LIST(blog)</p>\nINCLUDE_LIST(unknown)</main>", None);
+
+	assert!(html.starts_with("<main>\n<ul class=\"ssite-list\">\n"), "markdown <p> unwrapped");
+	assert!(html.contains(
+		"<li><a href=\"/blog/third-post\">Third post</a> <time datetime=\"2022-10-06\">2022-10-06</time></li>\n"
+	));
+	assert!(!html.contains("first-post"), "per_page = 2");
+	assert!(html.contains(
+		"<nav class=\"ssite-pagination\"><span>Page 1 of 2</span> <a href=\"/blog/page/2\" rel=\"next\">Next</a></nav>\n</main>"
+	));
+
+	Ok(())
+}
+
+#[test]
+fn collections_test_write_list_pages() -> Result<()> {
+	let site = Site::from_dir(Path::new("./tests-data/site-a"))?;
+	site.set_collections(Collections::load(&site));
+
+	let mut report = BuildReport::default();
+	let files = write_list_pages(&site, &mut report);
+	assert!(!report.has_failures());
+
+	let dist_dir = site.dist_dir();
+	let rel_files: Vec<&Path> = files.iter().filter_map(|f| f.strip_prefix(dist_dir).ok()).collect();
+	assert_eq!(
+		vec![
+			Path::new("blog/page/2"),
+			Path::new("blog/tags/rust"),
+			Path::new("blog/tags/web"),
+			Path::new("blog/tags/web-dev"),
+		],
+		rel_files
+	);
+
+	let page_2 = fs::read_to_string(dist_dir.join("blog/page/2"))?;
+	assert!(page_2.contains("Wrapped from root _frame.html"), "dir frames");
+	assert!(page_2.contains("<a href=\"/blog/first-post\">First post</a>"));
+	assert!(page_2.contains("<a href=\"/blog/\" rel=\"prev\">Previous</a> <span>Page 2 of 2</span>"));
+
+	let rust = fs::read_to_string(dist_dir.join("blog/tags/rust"))?;
+	assert!(rust.contains("second-post") && rust.contains("first-post") && !rust.contains("third-post"));
+
+	Ok(())
+}
+
+#[test]
+fn collections_test_list_page_error_reported() -> Result<()> {
+	let config = format!("{TEST_SITE_CONFIG}[[collection]]\ndir = \"blog\"\nper_page = 1\n");
+	let dir = new_test_dir(
+		"collections-list-error",
+		&[
+			("ssite.toml", &config),
+			// Note: Only the list pages have a page_number, so, only they fail.
+			("content/blog/_frame.html", "<main>{{#if page.page_number}}{{lookup}}{{/if}}INCLUDE_CONTENT</main>"),
+			("content/blog/first.md", "---\ntitle: First\n---\nfirst\n"),
+			("content/blog/second.md", "---\ntitle: Second\n---\nsecond\n"),
+		],
+	)?;
+	let rt = tokio::runtime::Runtime::new()?;
+
+	let site = Site::from_dir(&dir)?;
+	let report = rt.block_on(gen(&site, None))?;
+
+	assert_eq!(2, report.rendered);
+	assert_eq!(2, report.failed(), "the 2 list pages");
+	assert_eq!(Path::new("content/blog"), report.errors[0].src_file);
+	assert!(!site.dist_dir().join("blog/page/1").exists());
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
+
+#[test]
+fn collections_test_slugify() {
+	assert_eq!("web-dev", slugify(" Web Dev "));
+	assert_eq!("c-c", slugify("C++/C"));
+	assert_eq!("été", slugify("Été"));
+	assert_eq!("", slugify("++"));
+}
+
+#[test]
+fn collections_test_tag_without_slug_skipped() -> Result<()> {
+	let config = format!("{TEST_SITE_CONFIG}[[collection]]\ndir = \"blog\"\ntags = true\n");
+	let dir = new_test_dir(
+		"collections-empty-tag",
+		&[
+			("ssite.toml", &config),
+			("content/blog/first.md", "---\ntitle: First\ntags: [\"++\", \"#\", rust]\n---\nfirst\n"),
+		],
+	)?;
+
+	let site = Site::from_dir(&dir)?;
+	site.set_collections(Collections::load(&site));
+	let mut report = BuildReport::default();
+	let files = write_list_pages(&site, &mut report);
+
+	assert_eq!(vec![site.dist_dir().join("blog/tags/rust")], files);
+	assert!(!site.dist_dir().join("blog/tags").is_file());
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
@@ -0,0 +1,103 @@
+use super::*;
+use crate::gen::collisions::tests::_test_infra::{new_test_dir, TEST_SITE_CONFIG};
+use std::fs;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+fn test_site(name: &str, on_collision: &str) -> anyhow::Result<Site> {
+	let config = format!("{TEST_SITE_CONFIG}on_collision = \"{on_collision}\"\n");
+	let mut files = vec![("ssite.toml", config.as_str())];
+	for file in [
+		"content/hello.md",
+		"content/hello.html",
+		"content/other.md",
+		"content/docs/README.md",
+		"content/docs/index.html",
+	] {
+		files.push((file, "hello"));
+	}
+	let dir = new_test_dir(&format!("collisions-{name}"), &files)?;
+	Ok(Site::from_dir(&dir)?)
+}
+
+fn processors(site: &Site) -> Vec<FileProcessor> {
+	site.content_entries()
+		.filter_map(|entry| FileProcessor::from_src_file(site, entry.path().to_path_buf()))
+		.collect()
+}
+
+#[test]
+fn collisions_test_find() -> anyhow::Result<()> {
+	let site = test_site("find", "error")?;
+	let content_dir = site.content_dir().to_path_buf();
+
+	let collisions = find_collisions(processors(&site).iter());
+
+	assert_eq!(
+		vec![
+			Collision {
+				dist_file: site.dist_dir().join("docs/index.html"),
+				src_files: vec![content_dir.join("docs/README.md"), content_dir.join("docs/index.html")],
+			},
+			Collision {
+				dist_file: site.dist_dir().join("hello"),
+				src_files: vec![content_dir.join("hello.html"), content_dir.join("hello.md")],
+			},
+		],
+		collisions
+	);
+
+	// error names both files
+	let error = collisions[1].error(&site).to_string();
+	assert!(error.contains("content/hello.html and content/hello.md"), "{error}");
+
+	// single file (watch)
+	let processor = FileProcessor::from_src_file(&site, content_dir.join("hello.md")).unwrap();
+	assert_eq!(Some(&collisions[1]), find_collision(&site, &processor).as_ref());
+	let processor = FileProcessor::from_src_file(&site, content_dir.join("other.md")).unwrap();
+	assert_eq!(None, find_collision(&site, &processor));
+
+	let _ = fs::remove_dir_all(site.root_dir());
+
+	Ok(())
+}
+
+#[test]
+fn collisions_test_find_remaining_source() -> anyhow::Result<()> {
+	let site = test_site("remaining", "html")?;
+	let content_dir = site.content_dir().to_path_buf();
+
+	// the winner removed, the other file is the one to process
+	fs::remove_file(content_dir.join("hello.html"))?;
+	let processor = find_remaining_source(&site, &content_dir.join("hello.html"));
+	assert_eq!(Some(content_dir.join("hello.md").as_path()), processor.as_ref().map(|p| p.src_file()));
+
+	// no other file with the dist file
+	fs::remove_file(content_dir.join("other.md"))?;
+	assert!(find_remaining_source(&site, &content_dir.join("other.md")).is_none());
+
+	let _ = fs::remove_dir_all(site.root_dir());
+
+	Ok(())
+}
+
+#[test]
+fn collisions_test_winner() -> anyhow::Result<()> {
+	let collision = Collision {
+		dist_file: PathBuf::from("_site/hello"),
+		src_files: vec![PathBuf::from("content/hello.html"), PathBuf::from("content/hello.md")],
+	};
+	assert_eq!(None, collision.winner(OnCollision::Error));
+	assert_eq!(Some(Path::new("content/hello.html")), collision.winner(OnCollision::Html));
+	assert_eq!(Some(Path::new("content/hello.md")), collision.winner(OnCollision::Markdown));
+
+	// no single precedence file (e.g., `hello` asset and `hello.md`)
+	let collision = Collision {
+		dist_file: PathBuf::from("_site/hello"),
+		src_files: vec![PathBuf::from("content/hello"), PathBuf::from("content/hello.md")],
+	};
+	assert_eq!(None, collision.winner(OnCollision::Html));
+
+	Ok(())
+}
@@ -0,0 +1,33 @@
+use super::ContentFilter;
+use anyhow::Result;
+use std::path::Path;
+
+#[test]
+fn content_filter_test_is_included() -> Result<()> {
+	let root_dir = Path::new("/no-root");
+	let content_dir = root_dir.join("content");
+	let filter = ContentFilter::new(
+		root_dir,
+		&content_dir,
+		&["*.psd".to_string(), "node_modules/".to_string(), "/drafts/".to_string()],
+		&["_redirects".to_string()],
+	)?;
+
+	let included = |path: &str, is_dir: bool| filter.is_included(&content_dir, &content_dir.join(path), is_dir);
+
+	assert!(included("index.html", false));
+	assert!(included("sub/_frame.html", false), "frames are included");
+	assert!(included("sub/page_frame.md", false));
+	assert!(included("_redirects", false), "include pattern");
+
+	assert!(!included("_partials", true), "underscore dir");
+	assert!(!included("_notes/todo.md", false), "file of underscore dir");
+	assert!(!included("sub/_draft.md", false), "underscore file");
+	assert!(!included("assets/logo.psd", false));
+	assert!(!included("node_modules", true));
+	assert!(!included("js/node_modules/pkg/index.js", false));
+	assert!(!included("drafts/page.md", false), "anchored pattern");
+	assert!(included("sub/drafts/page.md", false), "anchored pattern only at the content dir root");
+
+	Ok(())
+}
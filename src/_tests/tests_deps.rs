@@ -0,0 +1,21 @@
+use super::DepGraph;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn deps_test_dependents() -> Result<()> {
+	let mut graph = DepGraph::default();
+	graph.set_deps(Path::new("/c/a.md"), vec!["/c/a_frame.md".into(), "/c/_frame.html".into()]);
+	graph.set_deps(Path::new("/c/sub/b.md"), vec!["/c/sub/_frame.html".into(), "/c/_frame.html".into()]);
+
+	assert_eq!(vec![PathBuf::from("/c/a.md")], graph.dependents(Path::new("/c/a_frame.md")));
+	assert_eq!(
+		vec![PathBuf::from("/c/a.md"), PathBuf::from("/c/sub/b.md")],
+		graph.dependents(Path::new("/c/_frame.html"))
+	);
+
+	graph.remove_page(Path::new("/c/a.md"));
+	assert!(graph.dependents(Path::new("/c/a_frame.md")).is_empty());
+
+	Ok(())
+}
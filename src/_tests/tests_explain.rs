@@ -0,0 +1,102 @@
+use super::*;
+use crate::gen::explain::tests::_test_infra::{new_test_dir, TESTS_DATA_DIR, TEST_SITE_CONFIG};
+use anyhow::Result;
+use std::fs;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+#[test]
+fn explain_test_page_frame_and_dirs() -> Result<()> {
+	let site = Site::from_dir(Path::new(TESTS_DATA_DIR))?;
+	let explanation = explain(&site, Path::new("content/sub-frame/content-2.md"), true)?;
+
+	assert_eq!("PageMarkdown", explanation.src_type);
+	assert_eq!(Some("/sub-frame/content-2"), explanation.url.as_deref());
+
+	// lookup, the page frames (html first), then the dir frames
+	let checks: Vec<(&str, bool)> = explanation
+		.checks
+		.iter()
+		.map(|c| (c.file.to_str().unwrap(), c.found))
+		.collect();
+	assert_eq!(
+		vec![
+			("content/sub-frame/content-2_frame.html", false),
+			("content/sub-frame/content-2_frame.md", true),
+			("content/sub-frame/_frame.html", true),
+			("content/_frame.html", true),
+		],
+		checks
+	);
+	assert_eq!(Some(FramesStop::ContentDir), explanation.stop);
+	assert_eq!(3, explanation.frames.len());
+
+	// html, the page, then after each frame
+	assert_eq!(4, explanation.html_steps.len());
+	assert_eq!("<p>from sub-frame/content-2.md</p>", explanation.html_steps[0].trim());
+	assert!(explanation.html_steps[1].contains("<h2>Wrapped from content-2_frame.md</h2>"));
+	assert!(!explanation.html_steps[2].contains("<!DOCTYPE html>"));
+	assert!(explanation.html_steps[3].starts_with("<!DOCTYPE html>"));
+
+	// display
+	let text = explanation.to_string();
+	assert!(text.contains("stop: reached the content dir"));
+	assert!(text.contains("  1. content/sub-frame/content-2.md (page)\n  2. content/sub-frame/content-2_frame.md\n"));
+
+	Ok(())
+}
+
+#[test]
+fn explain_test_no_frames() -> Result<()> {
+	let site = Site::from_dir(Path::new(TESTS_DATA_DIR))?;
+
+	// doctype page
+	let explanation = explain(&site, Path::new("content/full.html"), false)?;
+	assert_eq!(Some(FramesStop::DoctypePage), explanation.stop);
+	assert!(explanation.checks.is_empty() && explanation.frames.is_empty());
+
+	// frame (not a page)
+	let explanation = explain(&site, Path::new("content/sub-frame/_frame.html"), false)?;
+	assert_eq!("FrameHtml", explanation.src_type);
+	assert_eq!(None, explanation.stop);
+	assert_eq!(None, explanation.dist_file);
+
+	// not a content file
+	assert!(explain(&site, Path::new("content/_partials/missing.hbs"), false).is_err());
+	assert!(explain(&site, Path::new("ssite.toml"), false).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn explain_test_doctype_dir_frame_is_not_last() -> Result<()> {
+	let dir = new_test_dir(
+		"explain-doctype",
+		&[
+			("ssite.toml", TEST_SITE_CONFIG),
+			("content/_frame.html", "<!DOCTYPE html>\n<html>INCLUDE_CONTENT</html>\n"),
+			("content/standalone/_frame.html", "<!DOCTYPE html>\n<main>INCLUDE_CONTENT</main>\n"),
+			("content/standalone/page.md", "hello\n"),
+		],
+	)?;
+
+	let site = Site::from_dir(&dir)?;
+	let explanation = explain(&site, &dir.join("content/standalone/page.md"), false)?;
+
+	// a doctype dir frame does not stop the lookup (the root frame is applied too)
+	assert_eq!(
+		vec![
+			PathBuf::from("content/standalone/_frame.html"),
+			PathBuf::from("content/_frame.html")
+		],
+		explanation.frames
+	);
+	assert_eq!(4, explanation.checks.len(), "2 page frames, 2 dir frames");
+	assert!(explanation.checks[2].doctype);
+	assert_eq!(Some(FramesStop::ContentDir), explanation.stop);
+
+	let _ = fs::remove_dir_all(&dir);
+
+	Ok(())
+}
@@ -0,0 +1,122 @@
+use super::{atom_xml, feed_entries, rss_xml, FeedBodies};
+use crate::gen::feed::tests::_test_infra::{new_test_dir, TEST_SITE_CONFIG};
+use crate::gen::{gen, BuildReport, FileProcessor};
+use crate::site::Site;
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+const FEED_CONFIG: &str = "[[feed]]\ndir = \"blog\"\ntitle = \"Blog\"\nbase_url = \"https://example.com\"\n";
+
+#[test]
+fn feed_test_entries() -> Result<()> {
+	let site = Site::from_dir(Path::new("./tests-data/site-a"))?;
+	let config = &site.feed_configs()[0];
+	assert_eq!("Site A team", config.author.as_deref().unwrap());
+
+	let entries = feed_entries(&site, config, &mut FeedBodies::default(), &mut BuildReport::default());
+
+	// Note: limit = 2, most recent first, without the draft and the undated index.
+	let urls: Vec<&str> = entries.iter().map(|e| e.url.as_str()).collect();
+	assert_eq!(vec!["/blog/third-post", "/blog/second-post"], urls);
+	assert_eq!("Second post", entries[1].title);
+	assert_eq!(Some("Jane"), entries[1].author.as_deref());
+	assert!(entries[1].content.contains("<p>Title: Second post</p>"), "rendered body");
+	assert!(!entries[1].content.contains("Wrapped from root _frame.html"), "no frames");
+
+	Ok(())
+}
+
+#[test]
+fn feed_test_xml() -> Result<()> {
+	let site = Site::from_dir(Path::new("./tests-data/site-a"))?;
+	let config = &site.feed_configs()[0];
+	let entries = feed_entries(&site, config, &mut FeedBodies::default(), &mut BuildReport::default());
+
+	let atom = atom_xml(config, "/blog/atom.xml", &entries);
+	assert!(atom.contains("  <title>Site A Blog</title>\n"));
+	assert!(atom.contains("  <link href=\"https://site-a.example.com/blog/atom.xml\" rel=\"self\"/>\n"));
+	assert!(atom.contains("  <updated>2022-10-06T00:00:00Z</updated>\n"));
+	assert!(atom.contains("    <id>https://site-a.example.com/blog/second-post</id>\n"));
+	assert!(atom.contains("    <author><name>Jane</name></author>\n"));
+	assert!(atom.contains("&lt;h1&gt;Third post&lt;/h1&gt;"), "escaped html content");
+
+	let rss = rss_xml(config, "/blog/rss.xml", &entries);
+	assert!(rss.contains("      <pubDate>Wed, 05 Oct 2022 08:30:00 +0000</pubDate>\n"));
+	assert!(rss.contains("      <dc:creator>Site A team</dc:creator>\n"), "feed author fallback");
+	assert_eq!(2, rss.matches("<item>").count());
+
+	Ok(())
+}
+
+#[test]
+fn feed_test_invalid_entries_reported() -> Result<()> {
+	let config = format!("{TEST_SITE_CONFIG}{FEED_CONFIG}atom = \"blog/atom.xml\"\n");
+	let dir = new_test_dir(
+		"feed-invalid",
+		&[
+			("ssite.toml", &config),
+			("content/blog/good.md", "---\ntitle: Good\ndate: 2022-10-06\n---\ngood\n"),
+			("content/blog/bad-date.md", "---\ntitle: Bad date\ndate: someday\n---\nbad date\n"),
+			("content/blog/bad-template.md", "---\ndate: 2022-10-07\n---\n{{#if page.title}}\n"),
+			("content/stale.html", "<p>stale</p>"),
+		],
+	)?;
+	let rt = tokio::runtime::Runtime::new()?;
+
+	let site = Site::from_dir(&dir)?;
+	let report = rt.block_on(gen(&site, None))?;
+	fs::remove_file(dir.join("content/stale.html"))?;
+	let report_2 = rt.block_on(gen(&site, None))?;
+
+	// the build is not aborted, and each bad post is reported once
+	let mut failed: Vec<String> = report.errors.iter().map(|e| e.src_file.display().to_string()).collect();
+	failed.sort();
+	assert_eq!(vec!["content/blog/bad-date.md", "content/blog/bad-template.md"], failed);
+	assert_eq!(1, report_2.removed, "the site dir is still cleaned");
+
+	let atom = fs::read_to_string(site.dist_dir().join("blog/atom.xml"))?;
+	assert_eq!(1, atom.matches("<entry>").count());
+	assert!(atom.contains("<title>Good</title>"));
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
+
+#[test]
+fn feed_test_bodies_cached() -> Result<()> {
+	let config = format!("{TEST_SITE_CONFIG}{FEED_CONFIG}");
+	let dir = new_test_dir(
+		"feed-bodies",
+		&[("ssite.toml", &config), ("content/blog/post.md", "---\ndate: 2022-10-06\n---\nfirst\n")],
+	)?;
+	let site = Site::from_dir(&dir)?;
+	let config = &site.feed_configs()[0];
+	let src_file = site.content_dir().join("blog/post.md");
+	FileProcessor::from_src_file(&site, src_file.clone()).unwrap().process(&site)?;
+
+	let mut bodies = FeedBodies::default();
+	let mut report = BuildReport::default();
+	let entries = feed_entries(&site, config, &mut bodies, &mut report);
+	assert!(entries[0].content.contains("first"));
+
+	// the src changed, but the page is not re-rendered yet, so, the cached body
+	fs::write(&src_file, "---\ndate: 2022-10-06\n---\nsecond\n")?;
+	let entries = feed_entries(&site, config, &mut bodies, &mut report);
+	assert!(entries[0].content.contains("first"));
+
+	// re-rendered page, so, new body
+	std::thread::sleep(std::time::Duration::from_millis(10));
+	FileProcessor::from_src_file(&site, src_file).unwrap().process(&site)?;
+	let entries = feed_entries(&site, config, &mut bodies, &mut report);
+	assert!(entries[0].content.contains("second"));
+	assert!(!report.has_failures());
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
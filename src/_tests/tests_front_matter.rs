@@ -0,0 +1,70 @@
+use super::{parse_date, split_front_matter};
+use anyhow::Result;
+
+#[test]
+fn front_matter_test_split_yaml() -> Result<()> {
+	let content = "---\ntitle: Hello World\ndate: 2022-10-06\ndraft: true\ntags: [rust, web]\nextra:\n  version: 3\n---\n# Hello\n";
+
+	let (meta, body) = split_front_matter(content)?;
+
+	assert_eq!(Some("Hello World"), meta.title());
+	assert_eq!(Some("2022-10-06"), meta.date());
+	assert!(meta.draft());
+	assert_eq!(vec!["rust", "web"], meta.tags());
+	assert_eq!("# Hello\n", body);
+	assert_eq!(Some(3), meta.get("extra").and_then(|v| v.get("version")).and_then(|v| v.as_i64()));
+
+	Ok(())
+}
+
+#[test]
+fn front_matter_test_split_toml() -> Result<()> {
+	let content = "+++\ntitle = \"Hello <TOML>\"\ndate = 2022-10-06\nslug = \"hello\"\n+++\n<p>Hello</p>";
+
+	let (meta, body) = split_front_matter(content)?;
+
+	assert_eq!(Some("Hello <TOML>"), meta.title());
+	assert_eq!(Some("2022-10-06"), meta.date());
+	assert_eq!(Some("hello"), meta.get("slug").and_then(|v| v.as_str()));
+	assert!(!meta.draft());
+	assert_eq!("<p>Hello</p>", body);
+
+	Ok(())
+}
+
+#[test]
+fn front_matter_test_no_front_matter() -> Result<()> {
+	let content = "Some content\n---\nnot a front matter\n---\n";
+
+	let (meta, body) = split_front_matter(content)?;
+
+	assert!(meta.values().is_empty());
+	assert_eq!(content, body);
+
+	Ok(())
+}
+
+#[test]
+fn front_matter_test_is_published() -> Result<()> {
+	let now = parse_date("2022-10-06T12:00:00Z").unwrap();
+
+	let (meta, _) = split_front_matter("---\ndraft: true\n---\nbody")?;
+	assert!(!meta.is_published(now)?);
+
+	let (meta, _) = split_front_matter("---\npublish_date: 2022-10-06\n---\nbody")?;
+	assert!(meta.is_published(now)?);
+
+	let (meta, _) = split_front_matter("+++\npublish_date = 2022-10-06T13:00:00Z\n+++\nbody")?;
+	assert!(!meta.is_published(now)?);
+
+	let (meta, _) = split_front_matter("---\npublish_date: 2022-10-07 08:00:00\n---\nbody")?;
+	assert!(!meta.is_published(now)?);
+
+	let (meta, _) = split_front_matter("---\npublish_date: next week\n---\nbody")?;
+	assert!(meta.is_published(now).is_err());
+
+	let (meta, _) = split_front_matter("body")?;
+	assert!(meta.is_published(now)?);
+
+	Ok(())
+}
@@ -0,0 +1,223 @@
+use super::deps::DepGraph;
+use super::report::BuildReport;
+use super::{clean, gen, handle_src_file_event, mark_dist_dir, process_files};
+use crate::consts::{DIST_MARKER_FILE_NAME, SSITE_DIR};
+use super::processor::FileProcessor;
+use crate::gen::tests::_test_infra::{new_site_a_test_dir, new_test_dir, TEST_SITE_CONFIG};
+use crate::site::Site;
+use std::fs;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+#[test]
+fn gen_test_process_files_in_order() -> anyhow::Result<()> {
+	let dir = new_site_a_test_dir("gen-process-order")?;
+	let site = Site::from_dir(&dir)?;
+	assert!(site.dist_dir().starts_with(&dir));
+	let processors: Vec<_> = site
+		.content_entries()
+		.filter_map(|entry| FileProcessor::from_src_file(&site, entry.path().to_path_buf()))
+		.map(|processor| (processor, Vec::new()))
+		.collect();
+
+	let results = process_files(&site, &processors);
+
+	assert_eq!(processors.len(), results.len());
+	for ((processor, _), result) in processors.iter().zip(results) {
+		assert_eq!(Some(processor.dist_file().to_path_buf()), result?);
+		assert!(processor.dist_file().is_file());
+	}
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
+
+#[test]
+fn gen_test_clean() -> anyhow::Result<()> {
+	let config = format!("{TEST_SITE_CONFIG}keep = [\"CNAME\", \".well-known/\"]\n");
+	let dir = new_test_dir("gen-clean", &[("ssite.toml", &config), ("content/", "")])?;
+	let site = Site::from_dir(&dir)?;
+	let dist_dir = site.dist_dir().to_path_buf();
+	let rt = tokio::runtime::Runtime::new()?;
+
+	// --- Not empty, and no marker (e.g., not created by ssite)
+	fs::write(dist_dir.join("index.html"), "not from ssite")?;
+	assert!(!mark_dist_dir(&site)?);
+	assert!(rt.block_on(clean(&site)).is_err());
+	assert!(dist_dir.join("index.html").exists(), "not cleaned");
+	fs::write(dist_dir.join("stale"), "e.g., unpublished since")?;
+	let report = rt.block_on(gen(&site, None))?;
+	assert!(report.clean_skipped, "the build tells the clean was skipped");
+	assert!(dist_dir.join("stale").exists());
+	fs::remove_file(dist_dir.join("stale"))?;
+
+	// --- Empty, the marker is written
+	fs::remove_file(dist_dir.join("index.html"))?;
+	assert!(mark_dist_dir(&site)?);
+	assert!(dist_dir.join(DIST_MARKER_FILE_NAME).is_file());
+
+	// --- Marked, cleaned except the keep files
+	for file in ["index.html", "blog/tags/rust", "CNAME", ".well-known/security.txt"] {
+		let file = dist_dir.join(file);
+		fs::create_dir_all(file.parent().unwrap())?;
+		fs::write(file, "")?;
+	}
+	fs::create_dir_all(dir.join(SSITE_DIR))?;
+
+	assert_eq!(2, rt.block_on(clean(&site))?);
+	assert!(!dist_dir.join("index.html").exists());
+	assert!(!dist_dir.join("blog").exists(), "empty dirs removed");
+	assert!(dist_dir.join("CNAME").exists(), "kept");
+	assert!(dist_dir.join(".well-known/security.txt").exists(), "kept");
+	assert!(dist_dir.join(DIST_MARKER_FILE_NAME).exists(), "marker kept");
+	assert!(!dir.join(SSITE_DIR).exists(), "build cache removed");
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
+
+#[test]
+fn gen_test_output_collision() -> anyhow::Result<()> {
+	let dir = new_test_dir(
+		"gen-collision",
+		&[
+			("ssite.toml", TEST_SITE_CONFIG),
+			("content/hello.md", "from md"),
+			("content/hello.html", "<p>from html</p>"),
+		],
+	)?;
+	let rt = tokio::runtime::Runtime::new()?;
+
+	// --- Default, both files fail (no silent winner)
+	let site = Site::from_dir(&dir)?;
+	let report = rt.block_on(gen(&site, None))?;
+	assert_eq!(1, report.failed());
+	assert!(report.errors[0].cause.to_string().contains("content/hello.html and content/hello.md"));
+	assert!(!site.dist_dir().join("hello").exists());
+
+	// --- Precedence
+	let config = fs::read_to_string(dir.join("ssite.toml"))?;
+	fs::write(dir.join("ssite.toml"), config.replace("[source]\n", "[source]\non_collision = \"markdown\"\n"))?;
+	let site = Site::from_dir(&dir)?;
+	let report = rt.block_on(gen(&site, None))?;
+	assert!(!report.has_failures());
+	assert_eq!(1, report.rendered);
+	assert!(fs::read_to_string(site.dist_dir().join("hello"))?.contains("from md"));
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
+
+#[test]
+fn gen_test_generated_file_wins() -> anyhow::Result<()> {
+	let config = format!("{TEST_SITE_CONFIG}[sitemap]\nbase_url = \"https://example.com\"\n");
+	let dir = new_test_dir(
+		"gen-generated-wins",
+		&[
+			("ssite.toml", &config),
+			("content/index.html", "<p>home</p>"),
+			("content/sitemap.xml", "<urlset>hand maintained</urlset>"),
+		],
+	)?;
+	let rt = tokio::runtime::Runtime::new()?;
+
+	let site = Site::from_dir(&dir)?;
+	let report = rt.block_on(gen(&site, None))?;
+
+	assert!(!report.has_failures());
+	assert_eq!(0, report.copied, "the content sitemap.xml is skipped");
+	let sitemap = fs::read_to_string(site.dist_dir().join("sitemap.xml"))?;
+	assert!(sitemap.contains("<loc>https://example.com/</loc>"));
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
+
+#[test]
+fn gen_test_watch_collisions() -> anyhow::Result<()> {
+	let config = format!("{TEST_SITE_CONFIG}on_collision = \"html\"\n[sitemap]\nbase_url = \"https://example.com\"\n");
+	let dir = new_test_dir(
+		"gen-watch-collisions",
+		&[
+			("ssite.toml", &config),
+			("content/index.html", "<p>home</p>"),
+			("content/hello.md", "from md"),
+			("content/hello.html", "<p>from html</p>"),
+		],
+	)?;
+	let rt = tokio::runtime::Runtime::new()?;
+	let site = Site::from_dir(&dir)?;
+	rt.block_on(gen(&site, None))?;
+	assert!(fs::read_to_string(site.dist_dir().join("hello"))?.contains("from html"));
+
+	let mut dep_graph = DepGraph::default();
+	let mut report = BuildReport::default();
+
+	// --- A content file with the dist file of a generated file is skipped
+	let src_file = site.content_dir().join("sitemap.xml");
+	fs::write(&src_file, "<urlset>hand maintained</urlset>")?;
+	let dist_files = rt.block_on(handle_src_file_event(&site, &mut dep_graph, &src_file, &mut report))?;
+	assert!(dist_files.is_empty());
+	assert!(fs::read_to_string(site.dist_dir().join("sitemap.xml"))?.contains("<loc>https://example.com/</loc>"));
+
+	// --- Removing the winner of a collision processes the other file
+	let src_file = site.content_dir().join("hello.html");
+	fs::remove_file(&src_file)?;
+	let dist_files = rt.block_on(handle_src_file_event(&site, &mut dep_graph, &src_file, &mut report))?;
+	assert_eq!(vec![site.dist_dir().join("hello")], dist_files);
+	assert!(fs::read_to_string(site.dist_dir().join("hello"))?.contains("from md"));
+	assert!(!report.has_failures());
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
+
+#[test]
+fn gen_test_cache_link_targets() -> anyhow::Result<()> {
+	let dir = new_test_dir(
+		"gen-cache-links",
+		&[
+			("ssite.toml", TEST_SITE_CONFIG),
+			("content/a.md", "[see b](./b.md)"),
+			("content/b.md", "from b"),
+		],
+	)?;
+	let rt = tokio::runtime::Runtime::new()?;
+	let site = Site::from_dir(&dir)?;
+	let a_html = || fs::read_to_string(site.dist_dir().join("a"));
+
+	rt.block_on(gen(&site, None))?;
+	assert!(a_html()?.contains("href=\"b\""));
+
+	// --- Unchanged inputs, cached
+	let report = rt.block_on(gen(&site, None))?;
+	assert_eq!(2, report.unchanged);
+
+	// --- The linked page removed, the link is not rewritten anymore
+	fs::remove_file(dir.join("content/b.md"))?;
+	let report = rt.block_on(gen(&site, None))?;
+	assert_eq!(1, report.rendered);
+	assert!(a_html()?.contains("href=\"./b.md\""));
+
+	// --- Added back
+	fs::write(dir.join("content/b.md"), "from b")?;
+	rt.block_on(gen(&site, None))?;
+	assert!(a_html()?.contains("href=\"b\""));
+
+	// --- Ignored by the .ssiteignore
+	fs::write(dir.join(".ssiteignore"), "b.md\n")?;
+	let site = Site::from_dir(&dir)?;
+	let report = rt.block_on(gen(&site, None))?;
+	assert_eq!(1, report.rendered);
+	assert!(fs::read_to_string(site.dist_dir().join("a"))?.contains("href=\"./b.md\""));
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
@@ -1,9 +1,10 @@
 use super::FileProcessor;
-use crate::gen::processor::tests::_test_infra::TESTS_DATA_DIR;
-use crate::site::Site;
+use crate::gen::processor::tests::_test_infra::{new_test_dir, TESTS_DATA_DIR, TEST_SITE_CONFIG};
+use crate::site::{RunMode, Site};
 use std::fs;
 use std::path::Path;
 
+#[allow(clippy::duplicate_mod)]
 mod _test_infra;
 
 const CONTENT_WITH_FRAMES_COUNT: &[(&str, usize); 9] = &[
@@ -67,3 +68,200 @@ fn test_processor_process_page() -> anyhow::Result<()> {
 
 	Ok(())
 }
+
+#[test]
+fn test_processor_process_front_matter() -> anyhow::Result<()> {
+	let site = Site::from_dir(Path::new(TESTS_DATA_DIR))?;
+
+	for (file, title, body) in [
+		("front-matter/page-yaml.md", "Page from YAML", "<p>from front-matter/page-yaml.md</p>"),
+		("front-matter/page-toml.html", "Page from TOML", "<p>from front-matter/page-toml.html</p>"),
+	] {
+		let fp = FileProcessor::from_src_file(&site, site.content_dir().join(file)).unwrap();
+		let content = fs::read_to_string(fp.process(&site)?.unwrap())?;
+
+		assert!(content.contains(&format!("<h1>{title}</h1>")), "{file} title");
+		assert!(content.contains(body), "{file} body");
+		assert!(!content.contains("title"), "{file} front matter should be stripped");
+	}
+
+	Ok(())
+}
+
+#[test]
+fn test_processor_process_handlebars() -> anyhow::Result<()> {
+	let site = Site::from_dir(Path::new(TESTS_DATA_DIR))?;
+
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join("templates/page.md")).unwrap();
+	let content = fs::read_to_string(fp.process(&site)?.unwrap())?;
+
+	assert!(content.contains(r#"<main data-url="/templates/page">"#), "page_url in frame");
+	assert!(content.contains("<h1>Templated Page | Site A</h1>"), "page and site values in frame");
+	assert!(content.contains("<h1>Templated Page</h1>"), "page values in markdown page");
+	assert!(content.contains("Root path: ../"), "root_path");
+	assert!(content.contains("<footer>Site A - footer partial</footer>"), "partial");
+
+	Ok(())
+}
+
+#[test]
+fn test_processor_process_template_opt_out() -> anyhow::Result<()> {
+	let dir = new_test_dir(
+		"processor-template",
+		&[
+			("ssite.toml", &format!("{TEST_SITE_CONFIG}template = false\n[site]\ntitle = \"Site T\"\n")),
+			("content/_frame.html", "<main>{{site.title}} INCLUDE_CONTENT</main>\n"),
+			("content/raw.html", "<script>const t = `{{name}}`;</script>\n"),
+			("content/templated.html", "---\ntemplate: true\n---\n<h1>{{site.title}}</h1>\n"),
+		],
+	)?;
+	let site = Site::from_dir(&dir)?;
+
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join("raw.html")).unwrap();
+	let content = fs::read_to_string(fp.process(&site)?.unwrap())?;
+	assert!(content.contains("<script>const t = `{{name}}`;</script>"), "page not templated");
+	assert!(content.contains("<main>{{site.title}} "), "frame not templated");
+
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join("templated.html")).unwrap();
+	let content = fs::read_to_string(fp.process(&site)?.unwrap())?;
+	assert!(content.contains("<h1>Site T</h1>"), "front matter template: true");
+
+	let _ = fs::remove_dir_all(&dir);
+
+	Ok(())
+}
+
+#[test]
+fn test_processor_process_data() -> anyhow::Result<()> {
+	let site = Site::from_dir(Path::new(TESTS_DATA_DIR))?;
+
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join("templates/data.md")).unwrap();
+	let content = fs::read_to_string(fp.process(&site)?.unwrap())?;
+
+	assert!(content.contains("<p>Version: 1.2.0</p>"), "data value");
+	assert!(content.contains(r#"<a href="/blog/">Blog</a>"#), "data list");
+
+	let data_dir = site.data_dir();
+	let deps = fp.deps(&site)?;
+	assert!(deps.contains(&data_dir.join("info.json")), "data file dep");
+	assert!(deps.contains(&data_dir.join("nav/main.yaml")), "data file dep (sub dir)");
+	assert!(!deps.contains(&data_dir.join("social.toml")), "unused data file");
+
+	Ok(())
+}
+
+#[test]
+fn test_processor_deps_data_through_partials() -> anyhow::Result<()> {
+	let dir = new_test_dir(
+		"processor-deps-partials",
+		&[
+			("ssite.toml", TEST_SITE_CONFIG),
+			("content/_data/nav.yaml", "- title: Home\n  url: /\n"),
+			("content/_data/info.json", "{\"version\": \"1.0\"}"),
+			("content/_partials/nav.html", "{{#each data.nav}}<a href=\"{{url}}\">{{title}}</a>{{/each}}"),
+			("content/_partials/header.html", "<header>{{> nav}}</header>"),
+			("content/page.html", "{{> header}}<p>page</p>"),
+			("content/all.html", "<ul>{{#each data}}<li>{{@key}}</li>{{/each}}</ul>"),
+		],
+	)?;
+	let site = Site::from_dir(&dir)?;
+	let data_dir = site.data_dir();
+
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join("page.html")).unwrap();
+	let deps = fp.deps(&site)?;
+	assert!(deps.contains(&data_dir.join("nav.yaml")), "data of a nested partial");
+	assert!(!deps.contains(&data_dir.join("info.json")), "unused data file");
+
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join("all.html")).unwrap();
+	let deps = fp.deps(&site)?;
+	assert!(deps.contains(&data_dir.join("nav.yaml")) && deps.contains(&data_dir.join("info.json")), "data without a name");
+
+	let _ = fs::remove_dir_all(&dir);
+
+	Ok(())
+}
+
+#[test]
+fn test_processor_process_slots() -> anyhow::Result<()> {
+	let site = Site::from_dir(Path::new(TESTS_DATA_DIR))?;
+
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join("slots/page.md")).unwrap();
+	let content = fs::read_to_string(fp.process(&site)?.unwrap())?;
+
+	assert!(content.contains("\n<h1>Title from slots/page.md</h1>\n"), "page slot (unwrapped)");
+	assert!(content.contains("\nNo sidebar\n"), "frame default slot (unwrapped)");
+	assert!(content.contains("<p>from slots/page.md</p>"), "page content");
+	assert!(!content.contains("SLOT"), "no slot markers left");
+
+	Ok(())
+}
+
+#[test]
+fn test_processor_is_published() -> anyhow::Result<()> {
+	let site = Site::from_dir(Path::new(TESTS_DATA_DIR))?;
+	let dev_site = Site::from_dir(Path::new(TESTS_DATA_DIR))?.with_run_mode(RunMode::Dev);
+
+	for (path, published) in [("drafts/draft.md", false), ("drafts/future.md", false), ("drafts/published.md", true)] {
+		let src = site.content_dir().join(path);
+		let fp = FileProcessor::from_src_file(&site, src).unwrap();
+		assert_eq!(published, fp.is_published(&site)?, "build mode - {path}");
+		assert!(fp.is_published(&dev_site)?, "dev mode - {path}");
+	}
+
+	Ok(())
+}
+
+#[test]
+fn test_processor_dist_file_url_style() -> anyhow::Result<()> {
+	// site-b is the site-a content with the "directory" url_style
+	let site = Site::from_dir(Path::new("./tests-data/site-b"))?;
+
+	for (path, dist) in [
+		("hello.md", "hello/index.html"),
+		("hello2.html", "hello2/index.html"),
+		("index.html", "index.html"),
+		("sub-dir/content.html", "sub-dir/content/index.html"),
+		("info.json", "info.json"),
+	] {
+		let fp = FileProcessor::from_src_file(&site, site.content_dir().join(path)).unwrap();
+		assert_eq!(site.dist_dir().join(dist), fp.dist_file(), "{path}");
+	}
+
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join("hello.md")).unwrap();
+	assert_eq!(Some("/hello/".to_string()), site.dist_url(fp.dist_file()));
+
+	Ok(())
+}
+
+#[test]
+fn test_processor_markdown_links() -> anyhow::Result<()> {
+	let page = "links/page.md";
+
+	let site = Site::from_dir(Path::new(TESTS_DATA_DIR))?;
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join(page)).unwrap();
+	let content = fs::read_to_string(fp.process(&site)?.unwrap())?;
+
+	for link in [
+		"../sub-frame/content-2",
+		"./#top",
+		"../hello?from=links",
+		"../sub-dir/",
+		"../",
+		"../assets/style.css",
+		// external (unchanged)
+		"https://example.com/page.md",
+	] {
+		assert!(content.contains(&format!("<a href=\"{link}\">")), "extensionless - {link}");
+	}
+
+	// site-b is the site-a content with the "directory" url_style
+	let site = Site::from_dir(Path::new("./tests-data/site-b"))?;
+	let fp = FileProcessor::from_src_file(&site, site.content_dir().join(page)).unwrap();
+	let content = fs::read_to_string(fp.process(&site)?.unwrap())?;
+
+	for link in ["../../sub-frame/content-2/", "../#top", "../../hello/?from=links", "../../sub-dir/", "../../assets/style.css"] {
+		assert!(content.contains(&format!("<a href=\"{link}\">")), "directory - {link}");
+	}
+
+	Ok(())
+}
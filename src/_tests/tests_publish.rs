@@ -0,0 +1,107 @@
+use super::{build_plan, publish, LocalFile};
+use crate::publish::tests::_test_infra::{new_test_dir, TEST_SITE_CONFIG};
+use crate::site::Site;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+fn local_file(key: &str, md5: &str) -> LocalFile {
+	LocalFile {
+		key: key.to_string(),
+		path: PathBuf::from(key),
+		md5: md5.to_string(),
+	}
+}
+
+#[test]
+fn publish_test_build_plan() -> Result<()> {
+	// --- Fixtures
+	let local_files = vec![
+		local_file("site-a/index.html", "aaa"),
+		local_file("site-a/hello", "bbb"),
+		local_file("site-a/new-page", "ccc"),
+	];
+	let remote_etags: HashMap<String, String> = HashMap::from_iter(vec![
+		("site-a/index.html".to_string(), "\"aaa\"".to_string()), // same (etag are quoted)
+		("site-a/hello".to_string(), "\"xxx\"".to_string()),      // changed
+		("site-a/old-page".to_string(), "\"ddd\"".to_string()),   // stale
+	]);
+
+	// --- Exec
+	let plan = build_plan(local_files, &remote_etags);
+
+	// --- Checks
+	let uploads: Vec<&str> = plan.uploads.iter().map(|f| f.key.as_str()).collect();
+	assert_eq!(vec!["site-a/hello", "site-a/new-page"], uploads);
+	assert_eq!(vec!["site-a/old-page"], plan.deletes);
+	assert_eq!(1, plan.unchanged);
+
+	Ok(())
+}
+
+/// Publish to a local S3-compatible server (e.g., MinIO), with an existing bucket. Run with:
+///
+/// ```sh
+/// AWS_ACCESS_KEY_ID=minioadmin AWS_SECRET_ACCESS_KEY=minioadmin \
+/// SSITE_TEST_S3_ENDPOINT=http://127.0.0.1:9000 SSITE_TEST_S3_BUCKET=ssite-test \
+/// cargo test publish_test_local_s3 -- --ignored
+/// ```
+#[tokio::test]
+#[ignore = "needs a local S3-compatible server (see SSITE_TEST_S3_ENDPOINT)"]
+async fn publish_test_local_s3() -> Result<()> {
+	let endpoint = std::env::var("SSITE_TEST_S3_ENDPOINT")?;
+	let bucket = std::env::var("SSITE_TEST_S3_BUCKET").unwrap_or_else(|_| "ssite-test".to_string());
+	let bucket_root = format!("publish-test-{}", std::process::id());
+	let publish_config = format!("[publish]\nbucket_name = \"{bucket}\"\nbucket_endpoint = \"{endpoint}\"\n");
+	let config = format!("{TEST_SITE_CONFIG}\n{publish_config}bucket_root = \"{bucket_root}\"\n");
+	let dir = new_test_dir(
+		"publish-local-s3",
+		&[
+			("ssite.toml", &config),
+			("content/", ""),
+			("_site/index.html", "<p>index</p>"),
+			("_site/hello", "<p>hello</p>"),
+		],
+	)?;
+	let site = Site::from_dir(&dir)?;
+
+	// --- First publish, all uploaded
+	let report = publish(&site, false, false).await?;
+	assert_eq!((2, 0, 0), (report.uploaded, report.deleted, report.unchanged));
+
+	// --- Changed, removed, and unchanged files
+	fs::write(dir.join("_site/hello"), "<p>hello changed</p>")?;
+	fs::remove_file(dir.join("_site/index.html"))?;
+	fs::write(dir.join("_site/new"), "<p>new</p>")?;
+
+	let report = publish(&site, true, false).await?;
+	assert_eq!((2, 1, 0), (report.uploaded, report.deleted, report.unchanged), "dry run");
+	let report = publish(&site, false, false).await?;
+	assert_eq!((2, 1, 0), (report.uploaded, report.deleted, report.unchanged));
+	let report = publish(&site, false, false).await?;
+	assert_eq!((0, 0, 2), (report.uploaded, report.deleted, report.unchanged));
+
+	// --- Without bucket_root, the stale keys of the bucket (e.g., the bucket_root ones above) are kept
+	let config = format!("{TEST_SITE_CONFIG}\n{publish_config}");
+	fs::write(dir.join("ssite.toml"), config)?;
+	let site = Site::from_dir(&dir)?;
+	let report = publish(&site, true, false).await?;
+	assert_eq!(0, report.deleted);
+	assert!(report.kept >= 2, "the {bucket_root}/ keys are kept");
+
+	// --- Clean up the bucket_root keys
+	fs::remove_file(dir.join("_site/hello"))?;
+	fs::remove_file(dir.join("_site/new"))?;
+	let config = format!("{TEST_SITE_CONFIG}\n{publish_config}bucket_root = \"{bucket_root}\"\n");
+	fs::write(dir.join("ssite.toml"), config)?;
+	let report = publish(&Site::from_dir(&dir)?, false, false).await?;
+	assert_eq!(2, report.deleted);
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
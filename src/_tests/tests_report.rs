@@ -0,0 +1,30 @@
+use super::BuildReport;
+use crate::site::Site;
+use crate::Error;
+use std::path::Path;
+
+#[test]
+fn report_test_grouped_errors() -> anyhow::Result<()> {
+	let site = Site::from_dir(Path::new("./tests-data/site-a"))?;
+	let content_dir = site.content_dir();
+
+	let mut report = BuildReport {
+		rendered: 3,
+		..Default::default()
+	};
+	report.add_error(&site, &content_dir.join("a.md"), Error::TemplateError("a.md: bad".into()));
+	report.add_error(&site, &content_dir.join("b.md"), Error::InvalidFrontMatter("bad yaml".into()));
+	report.add_error(&site, &content_dir.join("c.md"), Error::TemplateError("c.md: bad".into()));
+
+	assert!(report.has_failures());
+	assert_eq!(3, report.failed());
+	assert_eq!(Path::new("content/a.md"), report.errors[0].src_file);
+
+	let text = report.to_string();
+	assert!(text.starts_with("Build errors (3):"));
+	assert!(text.contains("  Template error (2):\n    - content/a.md\n"));
+	assert!(text.contains("  Invalid front matter (1):\n    - content/b.md\n        Invalid front matter. Cause: bad yaml"));
+	assert!(text.ends_with("pages rendered: 3, files copied: 0, unchanged: 0, unpublished: 0, files removed: 0, failed: 3"));
+
+	Ok(())
+}
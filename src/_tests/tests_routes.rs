@@ -0,0 +1,83 @@
+use super::*;
+use crate::gen::routes::tests::_test_infra::TESTS_DATA_DIR;
+use anyhow::Result;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+#[test]
+fn routes_test_site_a() -> Result<()> {
+	let site = Site::from_dir(Path::new(TESTS_DATA_DIR))?;
+	let routes = routes(&site)?;
+	let route = |src_file: &str| routes.iter().find(|r| r.src_file == Path::new(src_file)).unwrap();
+
+	// index and readme
+	let index = route("content/index.html");
+	assert_eq!("IndexHtml", index.src_type);
+	assert_eq!(Some(PathBuf::from("_site/index.html")), index.dist_file);
+	assert_eq!(Some("/"), index.url.as_deref());
+	let readme = route("content/links/README.md");
+	assert_eq!("ReadmeMarkdown", readme.src_type);
+	assert_eq!(Some("/links/"), readme.url.as_deref());
+
+	// frame chain, from the closest to the farthest
+	let page = route("content/sub-frame/content-2.md");
+	assert_eq!("PageMarkdown", page.src_type);
+	assert_eq!(Some("/sub-frame/content-2"), page.url.as_deref());
+	assert_eq!(
+		vec![
+			PathBuf::from("content/sub-frame/content-2_frame.md"),
+			PathBuf::from("content/sub-frame/_frame.html"),
+			PathBuf::from("content/_frame.html")
+		],
+		page.frames
+	);
+
+	// full html page (no frames) and asset
+	assert!(route("content/full.html").frames.is_empty());
+	let asset = route("content/assets/style.css");
+	assert_eq!("Other", asset.src_type);
+	assert_eq!(Some("/assets/style.css"), asset.url.as_deref());
+
+	// frames have no output
+	let frame = route("content/sub-frame/_frame.html");
+	assert_eq!("FrameHtml", frame.src_type);
+	assert_eq!(None, frame.dist_file);
+	assert_eq!(None, frame.url);
+
+	// drafts are listed, but unpublished
+	assert!(!route("content/drafts/draft.md").published);
+	assert!(route("content/drafts/published.md").published);
+
+	// sorted by src file
+	assert!(routes.windows(2).all(|w| w[0].src_file < w[1].src_file));
+
+	Ok(())
+}
+
+#[test]
+fn routes_test_table() -> Result<()> {
+	// site-b is the site-a content with the "directory" url_style
+	let site = Site::from_dir(Path::new("./tests-data/site-b"))?;
+	let table = routes_table(&routes(&site)?);
+	let lines: Vec<&str> = table.lines().collect();
+
+	assert!(lines[0].starts_with("SOURCE"));
+	// Note: The site-b content dir is outside of its root dir.
+	let line = lines.iter().find(|l| l.starts_with("../site-a/content/hello.md ")).unwrap();
+	let cells: Vec<&str> = line.split_whitespace().collect();
+	assert_eq!(
+		vec![
+			"../site-a/content/hello.md",
+			"PageMarkdown",
+			"_site/hello/index.html",
+			"/hello/",
+			"../site-a/content/_frame.html"
+		],
+		cells
+	);
+	let line = lines.iter().find(|l| l.starts_with("../site-a/content/drafts/draft.md ")).unwrap();
+	assert!(line.contains("(unpublished)"));
+
+	Ok(())
+}
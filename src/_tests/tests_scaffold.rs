@@ -0,0 +1,62 @@
+use super::*;
+use crate::scaffold::tests::_test_infra::new_test_dir;
+use anyhow::Result;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+#[test]
+fn scaffold_test_init_and_new() -> Result<()> {
+	let dir = new_test_dir("scaffold-init", &[])?;
+
+	let created = init_site(&dir, true)?;
+	assert_eq!(6, created.len());
+	assert!(fs::read_to_string(dir.join(CONFIG_FILE_NAME))?.contains("[runner.css]"));
+	assert!(init_site(&dir, false).is_err(), "existing ssite.toml");
+
+	// the scaffolded config is valid
+	let site = Site::from_dir(&dir)?;
+	assert!(site.runners().is_some());
+
+	let file = new_content(&site, "page", "blog/my-first-post", None)?;
+	assert_eq!(site.content_dir().join("blog/my-first-post.md"), file);
+	let content = fs::read_to_string(&file)?;
+	assert!(content.contains("title: \"My first post\"\n"));
+	assert!(content.contains("# {{page.title}}"), "page handlebars kept");
+	assert!(!content.contains("{{date}}"));
+
+	assert!(new_content(&site, "page", "blog/my-first-post.md", None).is_err(), "existing file");
+	assert!(new_content(&site, "post", "blog/other", None).is_err(), "missing archetype");
+
+	fs::remove_dir_all(&dir)?;
+
+	Ok(())
+}
+
+#[test]
+fn scaffold_test_fill_archetype() -> Result<()> {
+	let archetype = "---\ntitle: \"{{title}}\"\ndate: {{date}}\nslug: {{slug}}\n---\n# {{page.title}}\n";
+	let content = fill_archetype(archetype, "Say \"hi\"", "2022-10-06", "say-hi");
+
+	assert_eq!("---\ntitle: \"Say \\\"hi\\\"\"\ndate: 2022-10-06\nslug: say-hi\n---\n# {{page.title}}\n", content);
+
+	// backslashes, parsed back as is (YAML and TOML)
+	let title = r#"C:\new "dir"\"#;
+	let yaml = fill_archetype("title: \"{{title}}\"\n", title, "", "");
+	let yaml = yaml_rust::YamlLoader::load_from_str(&yaml)?;
+	assert_eq!(Some(title), yaml[0]["title"].as_str());
+	let toml = fill_archetype("title = \"{{title}}\"\n", title, "", "");
+	let toml: toml::Value = toml::from_str(&toml)?;
+	assert_eq!(Some(title), toml["title"].as_str());
+
+	Ok(())
+}
+
+#[test]
+fn scaffold_test_title_from_slug() -> Result<()> {
+	assert_eq!("My first post", title_from_slug("my-first-post"));
+	assert_eq!("Hello world", title_from_slug("hello_world"));
+	assert_eq!("", title_from_slug(""));
+
+	Ok(())
+}
@@ -0,0 +1,111 @@
+use super::{inject_live_reload_script, resolve_dist_file, DevServer, DistTarget, LiveReload, LIVE_RELOAD_SCRIPT_PATH};
+use crate::server::tests::_test_infra::{new_test_dir, TEST_SITE_CONFIG};
+use crate::site::Site;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
+
+// Note: The test content dir is used as a "dist dir" as it is static.
+const DIR: &str = "./tests-data/site-a/content";
+
+#[test]
+fn server_test_resolve_dist_file() -> Result<()> {
+	let dir = Path::new(DIR);
+
+	let file = |rel: &str| Some(DistTarget::File(dir.join(rel)));
+
+	assert_eq!(file("index.html"), resolve_dist_file(dir, "/"));
+	assert_eq!(file("sub-dir/index.html"), resolve_dist_file(dir, "/sub-dir/"));
+	assert_eq!(file("hello.md"), resolve_dist_file(dir, "/hello.md?v=1"));
+	assert_eq!(file("sub-dir/content.html"), resolve_dist_file(dir, "/sub%2Ddir/content.html"));
+
+	// dir without the trailing slash (the query is kept)
+	assert_eq!(Some(DistTarget::Redirect("/sub-dir/".into())), resolve_dist_file(dir, "/sub-dir"));
+	assert_eq!(Some(DistTarget::Redirect("/sub-dir/?v=1".into())), resolve_dist_file(dir, "/sub-dir?v=1"));
+	assert_eq!(None, resolve_dist_file(dir, "/not-found"));
+	assert_eq!(None, resolve_dist_file(dir, "/../ssite.toml"));
+	assert_eq!(None, resolve_dist_file(dir, "/%2E%2E/ssite.toml"));
+
+	Ok(())
+}
+
+#[test]
+fn server_test_inject_live_reload_script() -> Result<()> {
+	let script = format!("<script src=\"{LIVE_RELOAD_SCRIPT_PATH}\"></script>");
+
+	// before the last `</body>` (case insensitive)
+	let html = inject_live_reload_script(b"<html><body><p>hello</p></BODY></html>".to_vec());
+	assert_eq!(format!("<html><body><p>hello</p>{script}</BODY></html>"), String::from_utf8(html)?);
+
+	// at the end if no `</body>` (e.g., fragment)
+	let html = inject_live_reload_script(b"<p>hello</p>".to_vec());
+	assert_eq!(format!("<p>hello</p>{script}"), String::from_utf8(html)?);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn server_test_live_reload_notify() -> Result<()> {
+	let live_reload = LiveReload::new();
+	let mut rx = live_reload.tx.subscribe();
+	let dist_dir = Path::new("/site/_site");
+
+	live_reload.notify(dist_dir, &[dist_dir.join("index.html"), dist_dir.join("assets/style.css")]);
+	assert_eq!("/index.html\n/assets/style.css", rx.recv().await?);
+
+	// the files outside of the dist dir are not notified (nothing sent if none)
+	live_reload.notify(dist_dir, &[PathBuf::from("/other/page.html")]);
+	live_reload.notify(dist_dir, &[dist_dir.join("hello")]);
+	assert_eq!("/hello", rx.recv().await?);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn server_test_live_reload_events_endpoint() -> Result<()> {
+	let config = format!("{TEST_SITE_CONFIG}\n[dev]\nport = 0\n");
+	let dir = new_test_dir("server-events", &[("ssite.toml", &config), ("content/", "")])?;
+	let site = Site::from_dir(&dir)?;
+
+	let server = DevServer::bind(&site).await?;
+	let addr = server.listener.local_addr()?;
+	let live_reload = server.live_reload();
+	tokio::spawn(server.serve());
+
+	let mut stream = TcpStream::connect(addr).await?;
+	stream.write_all(b"GET /__ssite/livereload HTTP/1.1\r\nHost: localhost\r\n\r\n").await?;
+
+	// the event stream head (the subscription is done before)
+	let head = read_until(&mut stream, "\r\n\r\n").await?;
+	assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "{head}");
+	assert!(head.contains("Content-Type: text/event-stream"), "{head}");
+
+	// one `data:` line per path, and an empty line to end the event
+	live_reload.notify(site.dist_dir(), &[site.dist_dir().join("index.html"), site.dist_dir().join("hello")]);
+	let event = read_until(&mut stream, "\n\n").await?;
+	assert_eq!("data: /index.html\ndata: /hello\n\n", event);
+
+	let _ = std::fs::remove_dir_all(&dir);
+
+	Ok(())
+}
+
+/// Read the stream up to (and including) the end marker (fails after a few seconds).
+async fn read_until(stream: &mut TcpStream, end: &str) -> Result<String> {
+	let mut data: Vec<u8> = Vec::new();
+	let mut buf = [0u8; 256];
+	while !String::from_utf8_lossy(&data).ends_with(end) {
+		let n = timeout(Duration::from_secs(5), stream.read(&mut buf)).await??;
+		if n == 0 {
+			break;
+		}
+		data.extend_from_slice(&buf[..n]);
+	}
+	Ok(String::from_utf8(data)?)
+}
@@ -1,11 +1,12 @@
 use crate::prelude::*;
 use crate::site::config::tests::_test_infra::TESTS_DATA_DIR;
-use crate::site::config::{RunMode, RunnerConfig, SiteConfig};
+use crate::site::config::{BucketCred, DevConfig, PublishConfig, RunMode, RunnerConfig, SiteConfig, UrlStyle};
 use anyhow::Result;
 use std::collections::HashSet;
 use std::path::Path;
 use toml::Value;
 
+#[allow(clippy::duplicate_mod)]
 mod _test_infra;
 
 #[test]
@@ -26,7 +27,7 @@ fn site_config_test_from_toml() -> Result<()> {
     args = ["rollup", "-c"]
     watch_args = ["rollup", "-w"]  		
   "#;
-	let toml: Value = toml::from_str(&toml)?;
+	let toml: Value = toml::from_str(toml)?;
 
 	// --- Exec
 	let root_dir = Path::new(TESTS_DATA_DIR).to_path_buf();
@@ -40,7 +41,7 @@ fn site_config_test_from_toml() -> Result<()> {
 	assert!(content_dir.ends_with(&end_with));
 
 	let r_configs = site_config.runner_configs.as_ref().unwrap();
-	let runner = r_configs.get(0).unwrap();
+	let runner = r_configs.first().unwrap();
 	assert_eq!("pcss", runner.name);
 	assert_eq!("echo", runner.cmd);
 	assert_eq!(&vec!["pcss", "args"], runner.args.as_ref().unwrap());
@@ -55,14 +56,15 @@ fn site_config_test_runner_config_parse_simple() -> Result<()> {
     args = ["pcss", "args"]
     watch_args = ["pcss", "watch"]  
   "#;
-	let toml: Value = toml::from_str(&toml)?;
+	let toml: Value = toml::from_str(toml)?;
 
 	let runner_config = RunnerConfig::from_value("test_runner", &toml)?;
 
 	assert_eq!("echo", runner_config.cmd);
 	assert_eq!(&vec!["pcss", "args"], runner_config.args.as_ref().unwrap());
 	assert_eq!(&vec!["pcss", "watch"], runner_config.watch_args.as_ref().unwrap());
-	assert_eq!(&RunMode::Build, runner_config.run_modes.iter().next().unwrap());
+	// Note: The run_modes is a HashSet (Build, and Dev with the watch_args), so, no iteration order.
+	assert!(runner_config.run_modes.contains(&RunMode::Build));
 
 	Ok(())
 }
@@ -75,7 +77,7 @@ fn site_config_test_runner_config_parse_with_run_on() -> Result<()> {
     watch_args = ["pcss", "watch"]  
     run_on = ["Dev", "Build"]
   "#;
-	let toml: Value = toml::from_str(&toml)?;
+	let toml: Value = toml::from_str(toml)?;
 
 	let runner_config = RunnerConfig::from_value("test_runner", &toml)?;
 
@@ -86,3 +88,94 @@ fn site_config_test_runner_config_parse_with_run_on() -> Result<()> {
 
 	Ok(())
 }
+
+#[test]
+fn site_config_test_url_style() -> Result<()> {
+	let root_dir = Path::new(TESTS_DATA_DIR).to_path_buf();
+	let source = "[source]\ncontent_dir = \"content/\"\ndist_dir = \"_site\"\n";
+
+	// --- Default
+	let toml: Value = toml::from_str(source)?;
+	assert_eq!(UrlStyle::Extensionless, SiteConfig::from_value(root_dir.clone(), toml)?.url_style);
+
+	// --- Custom
+	let toml: Value = toml::from_str(&f!("{source}url_style = \"html\""))?;
+	assert_eq!(UrlStyle::Html, SiteConfig::from_value(root_dir.clone(), toml)?.url_style);
+
+	// --- Invalid
+	let toml: Value = toml::from_str(&f!("{source}url_style = \"pretty\""))?;
+	assert!(SiteConfig::from_value(root_dir, toml).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn site_config_test_dist_dir_guard() -> Result<()> {
+	let root_dir = Path::new(TESTS_DATA_DIR).to_path_buf();
+
+	// --- Valid
+	let toml: Value = toml::from_str("[source]\ncontent_dir = \"content/\"\ndist_dir = \"_site\"")?;
+	assert!(SiteConfig::from_value(root_dir.clone(), toml).is_ok());
+
+	// --- Invalid (the root dir, the content dir, or a parent dir of them)
+	for dist_dir in [".", "content/", "content/_partials", ".."] {
+		let toml: Value = toml::from_str(&f!("[source]\ncontent_dir = \"content/\"\ndist_dir = \"{dist_dir}\""))?;
+		assert!(SiteConfig::from_value(root_dir.clone(), toml).is_err(), "{dist_dir}");
+	}
+
+	Ok(())
+}
+
+#[test]
+fn site_config_test_dev_config() -> Result<()> {
+	// --- Default
+	let toml: Value = toml::from_str("")?;
+	let dev_config = DevConfig::from_value(&toml)?;
+	assert_eq!("127.0.0.1", dev_config.host);
+	assert_eq!(8080, dev_config.port);
+
+	// --- Custom
+	let toml = r#"
+		[dev]
+		host = "0.0.0.0"
+		port = 3000
+	"#;
+	let toml: Value = toml::from_str(toml)?;
+	let dev_config = DevConfig::from_value(&toml)?;
+	assert_eq!("0.0.0.0", dev_config.host);
+	assert_eq!(3000, dev_config.port);
+
+	// --- Invalid port and host
+	for invalid in ["[dev]\nport = 70000", "[dev]\nport = \"3000\"", "[dev]\nhost = 127"] {
+		let toml: Value = toml::from_str(invalid)?;
+		assert!(DevConfig::from_value(&toml).is_err(), "{invalid}");
+	}
+
+	Ok(())
+}
+
+#[test]
+fn site_config_test_publish_config() -> Result<()> {
+	let toml = r#"
+		bucket_type = "s3"
+		bucket_name = "test-ssite"
+		bucket_root = "/site-a/"
+		bucket_cred_type = "profile"
+		bucket_cred_profile = "jc-user"
+		bucket_endpoint = "http://127.0.0.1:9000"
+	"#;
+	let toml: Value = toml::from_str(toml)?;
+
+	let publish_config = PublishConfig::from_value(&toml)?;
+
+	assert_eq!("test-ssite", publish_config.bucket_name);
+	assert_eq!(Some("site-a"), publish_config.bucket_root.as_deref());
+	assert_eq!(BucketCred::Profile(s!("jc-user")), publish_config.bucket_cred);
+	assert_eq!(Some("http://127.0.0.1:9000"), publish_config.bucket_endpoint.as_deref());
+
+	// --- Invalid bucket type
+	let toml: Value = toml::from_str("bucket_type = \"gcs\"\nbucket_name = \"test-ssite\"")?;
+	assert!(PublishConfig::from_value(&toml).is_err());
+
+	Ok(())
+}
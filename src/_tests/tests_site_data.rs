@@ -0,0 +1,49 @@
+use super::*;
+use anyhow::Result;
+use serde_json::json;
+
+#[test]
+fn site_data_test_load() -> Result<()> {
+	let data_dir = Path::new("tests-data/site-a/content/_data");
+	let data = SiteData::new(data_dir)?;
+	let values = Value::Object(data.values());
+
+	assert_eq!(json!("1.2.0"), values["info"]["version"], "json");
+	assert_eq!(json!("/blog/"), values["nav"]["main"][1]["url"], "yaml (sub dir)");
+	assert_eq!(json!("https://github.com/site-a"), values["social"]["github"], "toml");
+
+	Ok(())
+}
+
+#[test]
+fn site_data_test_used_files() -> Result<()> {
+	let data_dir = Path::new("tests-data/site-a/content/_data");
+	let data = SiteData::new(data_dir)?;
+
+	assert_eq!(vec![data_dir.join("info.json")], data.used_files("Version {{data.info.version}}"));
+	assert_eq!(
+		vec![data_dir.join("nav/main.yaml")],
+		data.used_files("{{#each data.nav.main}}{{title}}{{/each}}")
+	);
+	assert!(data.used_files("{{page.title}} {{data.unknown}}").is_empty());
+	assert!(data.used_files("<p>Some data, data.info is not an expression</p>").is_empty());
+
+	// without a name, so, all of the data files
+	let all = vec![data_dir.join("info.json"), data_dir.join("nav/main.yaml"), data_dir.join("social.toml")];
+	assert_eq!(all, data.used_files("{{#each data}}{{@key}}{{/each}}"));
+	assert_eq!(all, data.used_files("{{lookup data \"info\"}}"));
+	assert_eq!(all, data.used_files("{{> nav\n  items=data}}"));
+
+	Ok(())
+}
+
+#[test]
+fn site_data_test_insert_value() -> Result<()> {
+	let mut values = Map::new();
+	assert!(insert_value(&mut values, &["nav", "main"], json!([])).is_ok());
+	assert!(insert_value(&mut values, &["nav", "footer"], json!([])).is_ok());
+	assert!(insert_value(&mut values, &["nav"], json!({})).is_err(), "already defined");
+	assert!(insert_value(&mut values, &["nav", "main", "sub"], json!({})).is_err(), "not an object");
+
+	Ok(())
+}
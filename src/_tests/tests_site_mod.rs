@@ -1,8 +1,61 @@
 use super::*;
+use crate::site::tests::_test_infra::{new_test_dir, TEST_SITE_CONFIG};
 use anyhow::Result;
+use std::fs;
+
+#[allow(clippy::duplicate_mod)]
+mod _test_infra;
 
 #[test]
 fn site_test_site_from_dir() -> Result<()> {
 	let site = Site::from_dir(Path::new("tests-data/site-a"))?;
+	assert!(site.content_dir().ends_with("content"));
+	Ok(())
+}
+
+#[test]
+fn site_test_content_entries_filter() -> Result<()> {
+	let site = Site::from_dir(Path::new("tests-data/site-a"))?;
+	let content_dir = site.content_dir();
+	let files: Vec<PathBuf> = site
+		.content_entries()
+		.filter_map(|e| e.path().strip_prefix(content_dir).ok().map(|p| p.to_path_buf()))
+		.collect();
+	let has = |file: &str| files.iter().any(|f| f == Path::new(file));
+
+	assert!(has("index.html"));
+	assert!(has("_frame.html"), "frames are kept");
+	assert!(has("_redirects"), "[source] include");
+	assert!(has("assets/style.css"));
+	assert!(!has("assets/logo.psd"), ".ssiteignore");
+	assert!(!has("assets/.style.css.swp"), "[source] ignore");
+	assert!(!has("node_modules/pkg/index.js"), "[source] ignore dir");
+	assert!(!has("_notes/todo.md"), "underscore dir");
+	assert!(!has("_partials/footer.html"), "partials dir");
+
+	Ok(())
+}
+
+#[test]
+fn site_test_dist_content_type() -> Result<()> {
+	let dir = new_test_dir(
+		"site-content-type",
+		&[("ssite.toml", TEST_SITE_CONFIG), ("content/hello.md", "hello"), ("content/CNAME", "example.com")],
+	)?;
+
+	// extensionless url style, the pages are html, not the content files copied as is
+	let site = Site::from_dir(&dir)?;
+	assert_eq!("text/html; charset=utf-8", site.dist_content_type(&site.dist_dir().join("hello")));
+	assert_eq!("application/octet-stream", site.dist_content_type(&site.dist_dir().join("CNAME")));
+
+	// other url styles, the pages have an extension
+	let config = format!("{TEST_SITE_CONFIG}url_style = \"directory\"\n");
+	fs::write(dir.join("ssite.toml"), config)?;
+	let site = Site::from_dir(&dir)?;
+	assert_eq!("application/octet-stream", site.dist_content_type(&site.dist_dir().join("LICENSE")));
+	assert_eq!("text/html; charset=utf-8", site.dist_content_type(&site.dist_dir().join("hello/index.html")));
+
+	fs::remove_dir_all(&dir)?;
+
 	Ok(())
 }
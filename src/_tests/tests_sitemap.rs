@@ -0,0 +1,78 @@
+use super::{build_exclude, is_excluded, robots_txt, sitemap_urls, sitemap_xml, SitemapUrl};
+use crate::gen::deps::DepGraph;
+use crate::site::{Collections, Site};
+use anyhow::Result;
+use std::path::Path;
+
+#[test]
+fn sitemap_test_xml() -> Result<()> {
+	let urls = vec![
+		SitemapUrl {
+			url: "/".to_string(),
+			lastmod: Some("2022-10-06T10:00:00Z".to_string()),
+		},
+		SitemapUrl {
+			url: "/a&b".to_string(),
+			lastmod: None,
+		},
+	];
+
+	let xml = sitemap_xml("https://example.com", &urls);
+
+	assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+	assert!(xml.contains(
+		"  <url>\n    <loc>https://example.com/</loc>\n    <lastmod>2022-10-06T10:00:00Z</lastmod>\n  </url>\n"
+	));
+	assert!(xml.contains("  <url>\n    <loc>https://example.com/a&amp;b</loc>\n  </url>\n"));
+	assert!(xml.ends_with("</urlset>\n"));
+
+	assert_eq!(
+		"User-agent: *\nAllow: /\n\nSitemap: https://example.com/sitemap.xml\n",
+		robots_txt("https://example.com")
+	);
+
+	Ok(())
+}
+
+#[test]
+fn sitemap_test_exclude() -> Result<()> {
+	let exclude = build_exclude(&["/404".to_string(), "/private/".to_string(), "draft-*".to_string()])?;
+
+	assert!(is_excluded(&exclude, "/404"));
+	assert!(!is_excluded(&exclude, "/blog/404"), "anchored");
+	assert!(is_excluded(&exclude, "/private/"));
+	assert!(is_excluded(&exclude, "/private/page"));
+	assert!(is_excluded(&exclude, "/blog/draft-1"));
+	assert!(!is_excluded(&exclude, "/"));
+	assert!(!is_excluded(&exclude, "/blog/page"));
+
+	Ok(())
+}
+
+#[test]
+fn sitemap_test_urls() -> Result<()> {
+	let site = Site::from_dir(Path::new("./tests-data/site-a"))?;
+	let content_dir = site.content_dir();
+	let config = site.sitemap_config().unwrap();
+	assert_eq!("https://site-a.example.com", config.base_url);
+
+	let mut dep_graph = DepGraph::default();
+	for page in ["index.html", "hello.md", "full.html", "sub-dir/index.html", "sub-frame/content-1.html"] {
+		dep_graph.set_deps(&content_dir.join(page), Vec::new());
+	}
+
+	let urls: Vec<String> = sitemap_urls(&site, &dep_graph, config)?.into_iter().map(|u| u.url).collect();
+
+	// Note: `full` and `/sub-frame/` are excluded in the site-a config
+	assert_eq!(vec!["/", "/hello", "/sub-dir/"], urls);
+
+	// with the generated list pages
+	site.set_collections(Collections::load(&site));
+	let urls: Vec<String> = sitemap_urls(&site, &dep_graph, config)?.into_iter().map(|u| u.url).collect();
+	assert_eq!(
+		vec!["/", "/blog/page/2", "/blog/tags/rust", "/blog/tags/web", "/blog/tags/web-dev", "/hello", "/sub-dir/"],
+		urls
+	);
+
+	Ok(())
+}
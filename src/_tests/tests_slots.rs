@@ -0,0 +1,45 @@
+use super::{extract_slots, include_slots, unwrap_markdown_slots};
+use anyhow::Result;
+
+#[test]
+fn slots_test_extract_slots() -> Result<()> {
+	let content = "Before\nSLOT(head)\n<link rel=\"stylesheet\" href=\"page.css\">\nEND_SLOT\nMiddle\n  SLOT(title)\nMy Page\n  END_SLOT\nAfter";
+
+	let (slots, content) = extract_slots(content);
+
+	assert_eq!(Some("<link rel=\"stylesheet\" href=\"page.css\">"), slots.get("head"));
+	assert_eq!(Some("My Page"), slots.get("title"));
+	assert_eq!(None, slots.get("sidebar"));
+	assert_eq!("Before\nMiddle\nAfter", content);
+
+	Ok(())
+}
+
+#[test]
+fn slots_test_include_slots() -> Result<()> {
+	let (slots, _) = extract_slots("SLOT(title)\nMy Page\nEND_SLOT\n");
+	let frame = "<title>INCLUDE_SLOT(title)Default Title END_SLOT</title>\n<head>INCLUDE_SLOT(head)</head>\n<aside>INCLUDE_SLOT(sidebar)\n<p>Default sidebar</p>\nEND_SLOT</aside>";
+
+	let res = include_slots(frame, &slots);
+
+	assert_eq!(
+		"<title>My Page</title>\n<head></head>\n<aside><p>Default sidebar</p></aside>",
+		res
+	);
+
+	Ok(())
+}
+
+#[test]
+fn slots_test_unwrap_markdown_slots() -> Result<()> {
+	let html = "<p>INCLUDE_SLOT(head)</p>\n<h2>Title</h2>\n<p>INCLUDE_SLOT(footer)Default footer END_SLOT</p>\n<p>Other</p>";
+
+	let res = unwrap_markdown_slots(html);
+
+	assert_eq!(
+		"INCLUDE_SLOT(head)\n<h2>Title</h2>\nINCLUDE_SLOT(footer)Default footer END_SLOT\n<p>Other</p>",
+		res
+	);
+
+	Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::utils::rebase_path;
+use crate::utils::{content_type, md5_hex, percent_decode, rebase_path, relative_url};
 use std::path::Path;
 
 #[test]
@@ -16,3 +16,45 @@ fn test_rebase_path() -> Result<(), Box<dyn std::error::Error>> {
 
 	Ok(())
 }
+
+#[test]
+fn test_content_type() -> Result<(), Box<dyn std::error::Error>> {
+	assert_eq!("text/html; charset=utf-8", content_type(Path::new("_site/hello"), true));
+	assert_eq!("application/octet-stream", content_type(Path::new("_site/CNAME"), false));
+	assert_eq!("text/html; charset=utf-8", content_type(Path::new("_site/index.html"), false));
+	assert_eq!("application/json", content_type(Path::new("_site/info.json"), false));
+	assert_eq!("text/css", content_type(Path::new("_site/css/main.css"), false));
+
+	Ok(())
+}
+
+#[test]
+fn test_md5_hex() -> Result<(), Box<dyn std::error::Error>> {
+	assert_eq!("d41d8cd98f00b204e9800998ecf8427e", md5_hex(b""));
+	assert_eq!("5d41402abc4b2a76b9719d911017c592", md5_hex(b"hello"));
+
+	Ok(())
+}
+
+#[test]
+fn test_relative_url() -> Result<(), Box<dyn std::error::Error>> {
+	assert_eq!("../sub-frame/content-2", relative_url("/blog/hello", "/sub-frame/content-2"));
+	assert_eq!("../sub-dir/", relative_url("/blog/hello", "/sub-dir/"));
+	assert_eq!("other", relative_url("/blog/hello", "/blog/other"));
+	assert_eq!("./", relative_url("/blog/hello", "/blog/"));
+	assert_eq!("../", relative_url("/blog/hello", "/"));
+	assert_eq!("sub-dir/", relative_url("/", "/sub-dir/"));
+	assert_eq!("../../hello/", relative_url("/links/page/", "/hello/"));
+	assert_eq!("my%20page", relative_url("/hello", "/my page"));
+
+	Ok(())
+}
+
+#[test]
+fn test_percent_decode() -> Result<(), Box<dyn std::error::Error>> {
+	assert_eq!("my page.md", percent_decode("my%20page.md"));
+	assert_eq!("100%", percent_decode("100%"));
+	assert_eq!("é", percent_decode("%C3%A9"));
+
+	Ok(())
+}
@@ -0,0 +1,264 @@
+//! Link checker of the generated site (the html files of the dist dir).
+//!
+//! The relative and root relative `href` and `src` values are resolved against the dist files, as served
+//! (e.g., `/sub-dir/` is `sub-dir/index.html`), and their `#anchor` against the `id` (or `name`) attributes of the target.
+//! The external links (e.g., `https://...`, `mailto:...`) are not checked.
+
+use crate::consts::HTML_CONTENT_TYPE;
+use crate::gen::FileProcessor;
+use crate::prelude::*;
+use crate::site::Site;
+use crate::utils::{has_url_scheme, percent_decode};
+use pathdiff::diff_paths;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+// Note: Compiled once, as used for each html file of the dist dir.
+static LINK_RX: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r#"(?i)\s(?:href|src)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+static ANCHOR_RX: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r#"(?i)\s(?:id|name)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+
+#[derive(Debug, Default)]
+pub struct CheckReport {
+	/// html files checked.
+	pub files: usize,
+	/// internal links checked.
+	pub links: usize,
+	pub broken: Vec<BrokenLink>,
+}
+
+#[derive(Debug)]
+pub struct BrokenLink {
+	/// The src file of the page, relative to the root dir (the dist file for the generated pages).
+	pub file: PathBuf,
+	/// The link as in the html (e.g., "hello.md#intro").
+	pub link: String,
+	pub cause: BrokenCause,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BrokenCause {
+	MissingFile,
+	MissingAnchor,
+}
+
+impl CheckReport {
+	pub fn has_broken(&self) -> bool {
+		!self.broken.is_empty()
+	}
+}
+
+impl Display for CheckReport {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		if self.has_broken() {
+			let mut groups: BTreeMap<&Path, Vec<&BrokenLink>> = BTreeMap::new();
+			for broken in self.broken.iter() {
+				groups.entry(&broken.file).or_default().push(broken);
+			}
+
+			writeln!(f, "Broken links ({}):", self.broken.len())?;
+			for (file, brokens) in groups {
+				writeln!(f, "  - {}", file.display())?;
+				for broken in brokens {
+					writeln!(f, "      {} ({})", broken.link, broken.cause)?;
+				}
+			}
+		}
+
+		write!(
+			f,
+			"Check summary - files: {}, links: {}, broken: {}",
+			self.files,
+			self.links,
+			self.broken.len()
+		)
+	}
+}
+
+impl Display for BrokenCause {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			BrokenCause::MissingFile => write!(f, "file not found"),
+			BrokenCause::MissingAnchor => write!(f, "anchor not found"),
+		}
+	}
+}
+
+/// Check the internal links of the html files of the site dist dir.
+pub fn check(site: &Site) -> Result<CheckReport> {
+	let dist_dir = site.dist_dir();
+	let dist_files: HashSet<PathBuf> = site.dist_entries().map(|e| e.path().to_path_buf()).collect();
+	let src_files = src_files_by_dist_file(site);
+
+	let mut html_files: Vec<&PathBuf> = dist_files.iter().filter(|file| is_html_file(site, file)).collect();
+	html_files.sort();
+
+	let mut report = CheckReport::default();
+	// target html file -> its anchors (loaded on demand)
+	let mut anchors: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+
+	for file in html_files {
+		let content = fs::read_to_string(file)?;
+		let page_url = site.dist_url(file).unwrap_or_default();
+		report.files += 1;
+
+		for link in html_links(&content) {
+			let (url_path, anchor) = match resolve_link(&page_url, &link) {
+				Some(resolved) => resolved,
+				None => continue,
+			};
+			report.links += 1;
+
+			let cause = match target_file(dist_dir, &dist_files, &url_path) {
+				None => Some(BrokenCause::MissingFile),
+				Some(target) => match anchor.as_deref() {
+					// Note: `#` and `#top` are the top of the page (per the html spec).
+					Some(anchor) if anchor != "top" && is_html_file(site, &target) => {
+						let target_anchors = match anchors.get(&target) {
+							Some(target_anchors) => target_anchors,
+							None => {
+								let target_anchors = html_anchors(&fs::read_to_string(&target)?);
+								anchors.entry(target).or_insert(target_anchors)
+							}
+						};
+						(!target_anchors.contains(anchor)).then_some(BrokenCause::MissingAnchor)
+					}
+					_ => None,
+				},
+			};
+
+			if let Some(cause) = cause {
+				let src_file = src_files
+					.get(file)
+					.cloned()
+					.unwrap_or_else(|| diff_paths(file, site.root_dir()).unwrap_or_else(|| file.to_path_buf()));
+				report.broken.push(BrokenLink {
+					file: src_file,
+					link,
+					cause,
+				});
+			}
+		}
+	}
+
+	Ok(report)
+}
+
+// region:    --- Links
+
+/// The `href` and `src` attribute values of the html (html unescaped).
+pub fn html_links(html: &str) -> Vec<String> {
+	attribute_values(html, &LINK_RX)
+}
+
+/// The `id` and `name` attribute values of the html.
+pub fn html_anchors(html: &str) -> HashSet<String> {
+	attribute_values(html, &ANCHOR_RX)
+		.into_iter()
+		.collect()
+}
+
+/// Resolve a link of the page (root relative url) to the root relative url path and the eventual anchor.
+/// Returns None for the external links (with a scheme or protocol relative) and the empty links.
+pub fn resolve_link(page_url: &str, link: &str) -> Option<(String, Option<String>)> {
+	let link = link.trim();
+	if link.is_empty() || link.starts_with("//") || has_url_scheme(link) {
+		return None;
+	}
+
+	let (path, anchor) = match link.split_once('#') {
+		Some((path, anchor)) => (path, Some(percent_decode(anchor)).filter(|a| !a.is_empty())),
+		None => (link, None),
+	};
+	let path = path.split('?').next().unwrap_or_default();
+
+	// same page (e.g., "#intro")
+	if path.is_empty() {
+		return Some((s!(page_url), anchor));
+	}
+
+	let path = if path.starts_with('/') {
+		s!(path)
+	} else {
+		let base = page_url.rfind('/').map(|idx| &page_url[..=idx]).unwrap_or("/");
+		f!("{base}{path}")
+	};
+
+	Some((normalize_url_path(&percent_decode(&path)), anchor))
+}
+
+/// The dist file served for the url path (the file, or the `index.html` of the dir).
+fn target_file(dist_dir: &Path, dist_files: &HashSet<PathBuf>, url_path: &str) -> Option<PathBuf> {
+	let rel_path = url_path.trim_start_matches('/');
+	let file = dist_dir.join(rel_path);
+	if !rel_path.is_empty() && !rel_path.ends_with('/') && dist_files.contains(&file) {
+		return Some(file);
+	}
+	let index_file = file.join("index.html");
+	dist_files.contains(&index_file).then_some(index_file)
+}
+
+// endregion: --- Links
+
+// region:    --- Utils
+
+/// dist file -> src file (relative to the root dir), for the report.
+fn src_files_by_dist_file(site: &Site) -> HashMap<PathBuf, PathBuf> {
+	site.content_entries()
+		.filter_map(|entry| FileProcessor::from_src_file(site, entry.path().to_path_buf()))
+		.filter_map(|processor| Some((processor.dist_file().to_path_buf(), processor.root_rel_src_file(site)?)))
+		.collect()
+}
+
+fn is_html_file(site: &Site, file: &Path) -> bool {
+	site.dist_content_type(file) == HTML_CONTENT_TYPE
+}
+
+fn attribute_values(html: &str, rx: &Regex) -> Vec<String> {
+	rx.captures_iter(html)
+		.filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+		.map(|m| html_unescape(m.as_str()))
+		.collect()
+}
+
+/// Resolve the `.` and `..` segments (e.g., "/blog/../hello" is "/hello"), keeping the trailing slash.
+fn normalize_url_path(path: &str) -> String {
+	let mut segments: Vec<&str> = Vec::new();
+	for segment in path.split('/') {
+		match segment {
+			"" | "." => (),
+			".." => {
+				segments.pop();
+			}
+			_ => segments.push(segment),
+		}
+	}
+
+	let is_dir = path.ends_with('/') || path.ends_with("/.") || path.ends_with("/..");
+	match (segments.is_empty(), is_dir) {
+		(true, _) => s!("/"),
+		(false, true) => f!("/{}/", segments.join("/")),
+		(false, false) => f!("/{}", segments.join("/")),
+	}
+}
+
+fn html_unescape(value: &str) -> String {
+	value
+		.replace("&quot;", "\"")
+		.replace("&apos;", "'")
+		.replace("&#39;", "'")
+		.replace("&lt;", "<")
+		.replace("&gt;", ">")
+		.replace("&amp;", "&")
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_check.rs"]
+mod tests;
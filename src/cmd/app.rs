@@ -1,16 +1,125 @@
-use clap::{crate_version, Arg, Command};
+use clap::{crate_version, Arg, ArgAction, Command};
 
 pub fn cmd_app() -> Command {
 	Command::new("ssite")
-		.version(&crate_version!()[..])
+		.version(crate_version!())
 		.arg(arg_root_dir())
 		.subcommand(sub_dev())
+		.subcommand(sub_build())
+		.subcommand(sub_publish())
+		.subcommand(sub_check())
+		.subcommand(sub_routes())
+		.subcommand(sub_explain())
+		.subcommand(sub_clean())
+		.subcommand(sub_init())
+		.subcommand(sub_new())
 }
 
 fn sub_dev() -> Command {
 	Command::new("dev").arg(arg_root_dir())
 }
 
+fn sub_build() -> Command {
+	Command::new("build")
+		.about("Run the build runners and generate the site once (no watch)")
+		.arg(arg_root_dir())
+		.arg(arg_check())
+}
+
+fn sub_publish() -> Command {
+	Command::new("publish")
+		.about("Build the site and sync the dist dir to the [publish] bucket")
+		.arg(arg_root_dir())
+		.arg(
+			Arg::new("dry_run")
+				.long("dry-run")
+				.action(ArgAction::SetTrue)
+				.help("Only print the files that would be uploaded and deleted"),
+		)
+		.arg(
+			Arg::new("delete_all")
+				.long("delete-all")
+				.action(ArgAction::SetTrue)
+				.help("Delete the stale keys of the whole bucket when no [publish] bucket_root is set"),
+		)
+		.arg(arg_check())
+}
+
+fn sub_check() -> Command {
+	Command::new("check")
+		.about("Check the internal links and assets of the generated dist dir")
+		.arg(arg_root_dir())
+}
+
+fn sub_routes() -> Command {
+	Command::new("routes")
+		.about("List the content files with their type, output file, url, and frames")
+		.arg(arg_root_dir())
+		.arg(
+			Arg::new("json")
+				.long("json")
+				.action(ArgAction::SetTrue)
+				.help("Print the routes as json (instead of a table)"),
+		)
+}
+
+fn sub_explain() -> Command {
+	Command::new("explain")
+		.about("Explain how a content file is rendered (e.g., 'ssite explain content/blog/my-post.md'): frame lookup and render order")
+		.arg(arg_root_dir())
+		.arg(
+			Arg::new("file")
+				.required(true)
+				.help("The content file, relative to the current dir or the root dir"),
+		)
+		.arg(
+			Arg::new("html")
+				.long("html")
+				.action(ArgAction::SetTrue)
+				.help("Also print the html of the page, then after each frame is applied"),
+		)
+}
+
+fn sub_clean() -> Command {
+	Command::new("clean")
+		.about("Remove the generated files of the dist dir (except the [source] keep ones) and the build cache")
+		.arg(arg_root_dir())
+}
+
+fn sub_init() -> Command {
+	Command::new("init")
+		.about("Scaffold a new site in the root dir (ssite.toml, content/, archetypes/)")
+		.arg(arg_root_dir())
+		.arg(
+			Arg::new("runners")
+				.long("runners")
+				.action(ArgAction::SetTrue)
+				.help("Add the runner stubs (css and js) to the ssite.toml"),
+		)
+}
+
+fn sub_new() -> Command {
+	Command::new("new")
+		.about("Create a new content file from an archetype (e.g., 'ssite new page blog/my-post')")
+		.arg(arg_root_dir())
+		.arg(
+			Arg::new("archetype")
+				.required(true)
+				.help("The archetype name, i.e., the archetypes dir file (e.g., 'page' for 'archetypes/page.md')"),
+		)
+		.arg(
+			Arg::new("path")
+				.required(true)
+				.help("The file path, relative to the content dir ('.md' if no extension)"),
+		)
+		.arg(
+			Arg::new("title")
+				.long("title")
+				.num_args(1)
+				.help("The title (default from the file name)"),
+		)
+}
+
 // region:    Common Args
 fn arg_root_dir() -> Arg {
 	Arg::new("root_dir")
@@ -20,4 +129,11 @@ fn arg_root_dir() -> Arg {
 		.help("The root dir where the driving ssite.toml resides")
 }
 
+fn arg_check() -> Arg {
+	Arg::new("check")
+		.long("check")
+		.action(ArgAction::SetTrue)
+		.help("Check the internal links and assets after the build (fails on broken links)")
+}
+
 // endregion: Common Args
@@ -1,14 +1,16 @@
+use crate::check::check;
 use crate::cmd::app::cmd_app;
-use crate::gen::gen;
+use crate::gen::{clean, explain, gen, routes, routes_table, BuildReport};
+use crate::publish::publish;
+use crate::scaffold::{init_site, new_content};
+use crate::server::DevServer;
 use crate::site::{RunMode, Site};
 use crate::utils::assert_valid_dir;
 use crate::{s, Error};
 use clap::ArgMatches;
 use std::env;
+use std::fs::create_dir_all;
 use std::path::Path;
-use std::process::Command;
-use std::time::Duration;
-use tokio::time::sleep;
 
 mod app;
 
@@ -28,11 +30,24 @@ pub async fn cmd_run() -> Result<(), Error> {
 		.map(|d| Path::new(d).to_path_buf())
 		.unwrap_or_else(|| env::current_dir().unwrap().to_path_buf());
 
+	// Note: The init sub command creates the root dir.
+	if let Some(("init", _)) = argm.subcommand() {
+		create_dir_all(&dir)?;
+	}
+
 	assert_valid_dir(&dir)?;
 
 	// execute the sub command
 	match argm.subcommand() {
-		Some(("dev", sub_cmd)) => exec_dev(&dir, &sub_cmd).await?,
+		Some(("dev", sub_cmd)) => exec_dev(&dir, sub_cmd).await?,
+		Some(("build", sub_cmd)) => exec_build(&dir, sub_cmd).await?,
+		Some(("publish", sub_cmd)) => exec_publish(&dir, sub_cmd).await?,
+		Some(("check", sub_cmd)) => exec_check(&dir, sub_cmd).await?,
+		Some(("routes", sub_cmd)) => exec_routes(&dir, sub_cmd).await?,
+		Some(("explain", sub_cmd)) => exec_explain(&dir, sub_cmd).await?,
+		Some(("clean", sub_cmd)) => exec_clean(&dir, sub_cmd).await?,
+		Some(("init", sub_cmd)) => exec_init(&dir, sub_cmd).await?,
+		Some(("new", sub_cmd)) => exec_new(&dir, sub_cmd).await?,
 		_ => {
 			cmd_app().print_long_help()?;
 			println!("\n");
@@ -42,18 +57,116 @@ pub async fn cmd_run() -> Result<(), Error> {
 	Ok(())
 }
 
-async fn exec_dev(dir: &Path, _argm: &ArgMatches) -> Result<(), Error> {
+async fn exec_build(dir: &Path, argm: &ArgMatches) -> Result<(), Error> {
 	let site = Site::from_dir(dir)?;
+	build(&site, dir, argm.get_flag("check")).await?;
+	Ok(())
+}
 
-	// if we have runners we execute them
-	if let Some(runners) = site.runners() {
-		// --- First the the runners for Build
-		for runner in runners.iter().filter(|r| r.has_run_mode(&RunMode::Build)) {
-			println!("Build - Run runner '{}'", runner.name());
-			let mut cmd = runner.get_build_command(dir);
-			cmd.spawn()?.wait();
+/// Run the build runners and generate the site (and check its links if `check`). Fails on build failures.
+/// Also fails to check a dist dir that was not cleaned (its stale files are not part of the site).
+async fn build(site: &Site, dir: &Path, check: bool) -> Result<BuildReport, Error> {
+	run_build_runners(site, dir)?;
+
+	let report = gen(site, None).await?;
+	println!("{report}");
+
+	if report.has_failures() {
+		return Err(Error::BuildFailed(report.failed()));
+	}
+
+	if check {
+		if report.clean_skipped {
+			return Err(Error::DistDirNotMarked(site.dist_dir().display().to_string()));
 		}
+		check_links(site)?;
+	}
+
+	Ok(report)
+}
+
+async fn exec_check(dir: &Path, _argm: &ArgMatches) -> Result<(), Error> {
+	let site = Site::from_dir(dir)?;
+	check_links(&site)
+}
+
+async fn exec_routes(dir: &Path, argm: &ArgMatches) -> Result<(), Error> {
+	let site = Site::from_dir(dir)?;
+	let routes = routes(&site)?;
+
+	if argm.get_flag("json") {
+		println!("{}", serde_json::to_string_pretty(&routes)?);
+	} else {
+		println!("{}", routes_table(&routes));
+	}
+
+	Ok(())
+}
+
+async fn exec_explain(dir: &Path, argm: &ArgMatches) -> Result<(), Error> {
+	let site = Site::from_dir(dir)?;
+
+	// Note: The file arg is required, so, always present.
+	let file = argm.get_one::<String>("file").map(|s| s.as_str()).unwrap_or_default();
+
+	let explanation = explain(&site, Path::new(file), argm.get_flag("html"))?;
+	println!("{explanation}");
+
+	Ok(())
+}
+
+async fn exec_publish(dir: &Path, argm: &ArgMatches) -> Result<(), Error> {
+	let site = Site::from_dir(dir)?;
+
+	// --- First, make sure the dist dir is up to date
+	// Note: A dist dir that was not cleaned might have stale files (e.g., pages unpublished since), so, not synced.
+	let report = build(&site, dir, argm.get_flag("check")).await?;
+	if report.clean_skipped {
+		return Err(Error::DistDirNotMarked(site.dist_dir().display().to_string()));
+	}
+
+	// --- Then, sync it to the bucket
+	let report = publish(&site, argm.get_flag("dry_run"), argm.get_flag("delete_all")).await?;
+	println!("{report}");
 
+	Ok(())
+}
+
+async fn exec_clean(dir: &Path, _argm: &ArgMatches) -> Result<(), Error> {
+	let site = Site::from_dir(dir)?;
+	let removed = clean(&site).await?;
+	println!("Clean summary - files removed: {removed}");
+	Ok(())
+}
+
+async fn exec_init(dir: &Path, argm: &ArgMatches) -> Result<(), Error> {
+	init_site(dir, argm.get_flag("runners"))?;
+	println!("\nSite initialized. Run 'ssite dev' to start the dev server.");
+	Ok(())
+}
+
+async fn exec_new(dir: &Path, argm: &ArgMatches) -> Result<(), Error> {
+	let site = Site::from_dir(dir)?;
+
+	// Note: The args are required, so, always present.
+	let archetype = argm.get_one::<String>("archetype").map(|s| s.as_str()).unwrap_or_default();
+	let path = argm.get_one::<String>("path").map(|s| s.as_str()).unwrap_or_default();
+	let title = argm.get_one::<String>("title").map(|s| s.as_str());
+
+	let file = new_content(&site, archetype, path, title)?;
+	println!("- create:     {}", file.display());
+
+	Ok(())
+}
+
+async fn exec_dev(dir: &Path, _argm: &ArgMatches) -> Result<(), Error> {
+	let site = Site::from_dir(dir)?.with_run_mode(RunMode::Dev);
+
+	// --- First the the runners for Build
+	run_build_runners(&site, dir)?;
+
+	// if we have runners we execute them
+	if let Some(runners) = site.runners() {
 		// --- Then the dev
 		for runner in runners.iter().filter(|r| r.has_run_mode(&RunMode::Dev)) {
 			let mut cmd = runner.get_watch_command(dir);
@@ -67,5 +180,40 @@ async fn exec_dev(dir: &Path, _argm: &ArgMatches) -> Result<(), Error> {
 			});
 		}
 	}
-	gen(&site, true).await
+	// --- Start the local http server on the dist dir
+	let server = DevServer::bind(&site).await?;
+	println!("Dev server - serving {} on {}", site.dist_dir().display(), server.local_url()?);
+	let live_reload = server.live_reload();
+	tokio::spawn(server.serve());
+
+	gen(&site, Some(&live_reload)).await?;
+
+	Ok(())
+}
+
+fn run_build_runners(site: &Site, dir: &Path) -> Result<(), Error> {
+	if let Some(runners) = site.runners() {
+		for runner in runners.iter().filter(|r| r.has_run_mode(&RunMode::Build)) {
+			println!("Build - Run runner '{}'", runner.name());
+			let mut cmd = runner.get_build_command(dir);
+			let status = cmd.spawn()?.wait()?;
+			if !status.success() {
+				return Err(Error::RunnerFailed(s!(runner.name()), status.code().unwrap_or(-1)));
+			}
+		}
+	}
+
+	Ok(())
+}
+
+/// Check the links of the dist dir, and print the report. Fails if broken links.
+fn check_links(site: &Site) -> Result<(), Error> {
+	let report = check(site)?;
+	println!("{report}");
+
+	if report.has_broken() {
+		Err(Error::CheckFailed(report.broken.len()))
+	} else {
+		Ok(())
+	}
 }
@@ -1,4 +1,15 @@
 //! Application constants
 
+pub const CONFIG_FILE_NAME: &str = "ssite.toml";
+pub const SSITEIGNORE_FILE_NAME: &str = ".ssiteignore";
 pub const FRAME: &str = "_frame.html";
+/// The ssite working dir (in the root dir), e.g., for the dependency graph.
+pub const SSITE_DIR: &str = ".ssite";
+/// The marker file of the dist dirs created by ssite (a dist dir is only cleaned if it has it, or is empty).
+pub const DIST_MARKER_FILE_NAME: &str = ".ssite-dist";
 pub const INCLUDE_CONTENT: &str = "INCLUDE_CONTENT";
+pub const INCLUDE_SLOT: &str = "INCLUDE_SLOT";
+pub const SLOT: &str = "SLOT";
+pub const END_SLOT: &str = "END_SLOT";
+pub const INCLUDE_LIST: &str = "INCLUDE_LIST";
+pub const HTML_CONTENT_TYPE: &str = "text/html; charset=utf-8";
@@ -1,26 +1,60 @@
+#[allow(clippy::enum_variant_names)]
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
 	#[error("Missing config file 'ssite.toml' in root dir {0} ")]
 	MissingConfigFile(String),
 
-	#[error("Missing content dir {0} ")]
-	MissingContentDir(String),
-
 	#[error("Missing config property {0}")]
 	MissingConfigProperty(String),
 
+	#[error("Invalid config value for {0}. Cause: {1}")]
+	InvalidConfigValue(String, String),
+
 	#[error("Invalid or missing S3 config")]
 	InvalidS3Config,
 
+	#[error("S3 error: {0}")]
+	S3Error(String),
+
 	#[error("Site root path {0} is not a valid directory path. Provide valid path with -d 'some/valid/dir/path'")]
 	SiteDirMissing(String),
 
-	#[error("Config error for the runner {0}. Cause: {1}")]
-	RunnerConfigError(String, String),
-
 	#[error("Invalid runner run_on value '{0}'. Must be 'Build' | 'Dev'")]
 	RunnerConfigErrorRunOn(String),
 
+	#[error("Runner {0} failed with exit code {1}")]
+	RunnerFailed(String, i32),
+
+	#[error("Output collision, {1} have the same dist file {0} (see [source] on_collision)")]
+	OutputCollision(String, String),
+
+	#[error("Build failed. {0} file(s) could not be processed")]
+	BuildFailed(usize),
+
+	#[error("Link check failed. {0} broken link(s)")]
+	CheckFailed(usize),
+
+	#[error("Invalid front matter. Cause: {0}")]
+	InvalidFrontMatter(String),
+
+	#[error("Template error {0}")]
+	TemplateError(String),
+
+	#[error("Data file error {0}")]
+	DataFileError(String),
+
+	#[error("File already exists {0}")]
+	FileAlreadyExists(String),
+
+	#[error("Missing archetype {0}")]
+	MissingArchetype(String),
+
+	#[error("File {0} is not a content file of the site (missing, outside of the content dir, or excluded)")]
+	NotContentFile(String),
+
+	#[error("Dist dir {0} is not empty and has no .ssite-dist marker file, so, it is not cleaned (add the marker file if its files are generated by ssite)")]
+	DistDirNotMarked(String),
+
 	#[error("Value for property {0} was not found.")]
 	TomlMissingValue(String),
 
@@ -36,6 +70,9 @@ pub enum Error {
 	#[error(transparent)]
 	TomlError(#[from] toml::de::Error),
 
+	#[error(transparent)]
+	JsonError(#[from] serde_json::Error),
+
 	#[error(transparent)]
 	EnumParseError(#[from] strum::ParseError),
 }
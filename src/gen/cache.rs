@@ -0,0 +1,184 @@
+//! Build cache, so that a full generation only re-processes the files whose inputs changed.
+//!
+//! Each processed source file is recorded with its content hash, the hashes of its dependencies (e.g., frames),
+//! and the hash of the site wide inputs (`ssite.toml`, `.ssiteignore`, and partials).
+//! Persisted in the `.ssite/` dir of the root dir.
+
+use crate::consts::{CONFIG_FILE_NAME, SSITEIGNORE_FILE_NAME, SSITE_DIR};
+use crate::prelude::*;
+use crate::site::Site;
+use crate::utils::md5_hex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+const CACHE_FILE_NAME: &str = "cache.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BuildCache {
+	entries: BTreeMap<PathBuf, CacheEntry>,
+
+	/// hash of the site wide inputs (config, `.ssiteignore`, and partials) for this build.
+	#[serde(skip)]
+	site_hash: String,
+	/// hashes computed during this build (e.g., the frames shared by many pages).
+	#[serde(skip)]
+	hashes: HashMap<PathBuf, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+	size: u64,
+	modified: u128,
+	hash: String,
+	deps: BTreeMap<PathBuf, String>,
+	site_hash: String,
+	dist_file: PathBuf,
+}
+
+impl BuildCache {
+	/// Determine if the dist file is up to date with the src file, its dependencies, and the site inputs.
+	pub fn is_fresh(&mut self, src_file: &Path, deps: &[PathBuf], dist_file: &Path) -> bool {
+		let entry = match self.entries.get_mut(src_file) {
+			Some(entry) => entry,
+			None => return false,
+		};
+
+		if entry.site_hash != self.site_hash || entry.dist_file != dist_file || !dist_file.exists() {
+			return false;
+		}
+
+		// Note: Fast path when the size and modified time did not change (avoid reading large files).
+		//       When only they changed (e.g., touched or checked out file), they are refreshed for the next builds.
+		let src_unchanged = match file_stamp(src_file) {
+			Some((size, modified)) if size == entry.size && modified == entry.modified => true,
+			Some((size, modified)) if md5_file(src_file).as_ref() == Some(&entry.hash) => {
+				entry.size = size;
+				entry.modified = modified;
+				true
+			}
+			_ => false,
+		};
+		if !src_unchanged || entry.deps.len() != deps.len() {
+			return false;
+		}
+
+		let entry_deps: Vec<(PathBuf, String)> = entry.deps.iter().map(|(p, h)| (p.clone(), h.clone())).collect();
+		entry_deps
+			.into_iter()
+			.all(|(dep, hash)| deps.contains(&dep) && self.hash(&dep).as_ref() == Some(&hash))
+	}
+
+	/// Record the inputs of a processed src file.
+	pub fn update(&mut self, src_file: &Path, deps: &[PathBuf], dist_file: &Path) {
+		let (hash, (size, modified)) = match (md5_file(src_file), file_stamp(src_file)) {
+			(Some(hash), Some(stamp)) => (hash, stamp),
+			_ => {
+				self.entries.remove(src_file);
+				return;
+			}
+		};
+
+		let deps = deps
+			.iter()
+			.filter_map(|dep| self.hash(dep).map(|hash| (dep.to_path_buf(), hash)))
+			.collect();
+
+		let entry = CacheEntry {
+			size,
+			modified,
+			hash,
+			deps,
+			site_hash: self.site_hash.to_string(),
+			dist_file: dist_file.to_path_buf(),
+		};
+		self.entries.insert(src_file.to_path_buf(), entry);
+	}
+
+	pub fn remove(&mut self, src_file: &Path) {
+		self.entries.remove(src_file);
+	}
+
+	/// Only keep the entries of the given src files (i.e., the ones of this build).
+	pub fn retain(&mut self, src_files: &HashSet<PathBuf>) {
+		self.entries.retain(|src_file, _| src_files.contains(src_file));
+	}
+
+	fn hash(&mut self, file: &Path) -> Option<String> {
+		if let Some(hash) = self.hashes.get(file) {
+			return Some(hash.to_string());
+		}
+		let hash = md5_file(file)?;
+		self.hashes.insert(file.to_path_buf(), hash.to_string());
+		Some(hash)
+	}
+}
+
+/// Persistence
+impl BuildCache {
+	pub fn file(site: &Site) -> PathBuf {
+		site.root_dir().join(SSITE_DIR).join(CACHE_FILE_NAME)
+	}
+
+	/// Load the persisted cache (empty if none or invalid), for the current site inputs.
+	pub fn load(site: &Site) -> BuildCache {
+		let mut cache: BuildCache = fs::read_to_string(Self::file(site))
+			.ok()
+			.and_then(|content| serde_json::from_str(&content).ok())
+			.unwrap_or_default();
+		cache.site_hash = site_hash(site);
+		cache
+	}
+
+	pub fn save(&self, site: &Site) -> Result<()> {
+		let file = Self::file(site);
+		if let Some(dir) = file.parent() {
+			fs::create_dir_all(dir)?;
+		}
+		fs::write(file, serde_json::to_string(self)?)?;
+		Ok(())
+	}
+}
+
+// region:    --- Utils
+
+/// Hash of the inputs that can change any page (config file, including its ignore and include patterns,
+/// the `.ssiteignore` file, as they change the content files and the links to them, and partials).
+fn site_hash(site: &Site) -> String {
+	let mut data: Vec<u8> = fs::read(site.root_dir().join(CONFIG_FILE_NAME)).unwrap_or_default();
+	data.extend(SSITEIGNORE_FILE_NAME.as_bytes());
+	data.extend(fs::read(site.root_dir().join(SSITEIGNORE_FILE_NAME)).unwrap_or_default());
+
+	let mut partials: Vec<PathBuf> = WalkDir::new(site.partials_dir())
+		.into_iter()
+		.filter_map(|e| e.ok().filter(|e| e.path().is_file()))
+		.map(|e| e.path().to_path_buf())
+		.collect();
+	partials.sort();
+	for partial in partials {
+		data.extend(partial.to_string_lossy().as_bytes());
+		data.extend(fs::read(&partial).unwrap_or_default());
+	}
+
+	md5_hex(&data)
+}
+
+fn md5_file(file: &Path) -> Option<String> {
+	fs::read(file).ok().map(|data| md5_hex(&data))
+}
+
+/// The (size, modified time in nanos) of the file.
+fn file_stamp(file: &Path) -> Option<(u64, u128)> {
+	let metadata = fs::metadata(file).ok()?;
+	let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+	Some((metadata.len(), modified))
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_cache.rs"]
+mod tests;
@@ -0,0 +1,307 @@
+//! Collections, i.e., the pages of a content dir (see the `[[collection]]` config sections, and `site::Collections`).
+//!
+//! - `INCLUDE_LIST(name)` in a page or frame is replaced by the sorted list of the collection pages
+//!   (the first list page, with the pagination nav, if paginated).
+//! - Generated list pages, with the frames of the collection dir:
+//!   the paginated lists (`/{dir}/page/2`, ...), and the tag lists (`/{dir}/tags/{tag}`).
+
+use super::front_matter::parse_date;
+use super::processor::{render_virtual_page, virtual_dist_file, FileProcessor};
+use super::report::BuildReport;
+use crate::consts::INCLUDE_LIST;
+use crate::prelude::*;
+use crate::site::{Collection, CollectionConfig, CollectionItem, CollectionSort, Collections, Site};
+use crate::utils::xml_escape;
+use regex::{Captures, Regex};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+// Note: Compiled once, as used for each page and frame render.
+static INCLUDE_LIST_RX: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(&f!(r"<p>{INCLUDE_LIST}\(([\w-]+)\)</p>|{INCLUDE_LIST}\(([\w-]+)\)")).unwrap());
+
+/// The items of a list, for a generated list page.
+#[derive(Debug)]
+pub struct ListPage {
+	/// The collection name.
+	pub name: String,
+	pub items: Vec<CollectionItem>,
+	/// 1 based page number.
+	pub page: usize,
+	pub total_pages: usize,
+}
+
+/// Makers
+impl Collections {
+	/// Load the collections of the site config.
+	/// Note: The pages with invalid front matter are skipped (reported by the page processing).
+	pub fn load(site: &Site) -> Collections {
+		let collections = site
+			.collection_configs()
+			.iter()
+			.map(|config| Collection::load(site, config))
+			.collect();
+		Collections { collections }
+	}
+}
+
+impl Collection {
+	fn load(site: &Site, config: &CollectionConfig) -> Collection {
+		let dir = site.content_dir().join(&config.dir);
+		let mut index_url = None;
+		let mut items = Vec::new();
+
+		for entry in site.content_entries().filter(|e| e.path().starts_with(&dir)) {
+			let processor = match FileProcessor::from_src_file(site, entry.path().to_path_buf()) {
+				Some(processor) if processor.is_for_html_render() => processor,
+				_ => continue,
+			};
+			if !processor.is_published(site).unwrap_or(false) {
+				continue;
+			}
+			let url = match site.dist_url(processor.dist_file()) {
+				Some(url) => url,
+				None => continue,
+			};
+
+			// the index pages are not items (the one of the collection dir is the first list page)
+			if processor.is_index() {
+				if entry.path().parent() == Some(dir.as_path()) {
+					index_url = Some(url);
+				}
+				continue;
+			}
+
+			let page_meta = match processor.page_meta() {
+				Ok(page_meta) => page_meta,
+				Err(_) => continue,
+			};
+
+			items.push(CollectionItem {
+				title: page_meta.title().map(|t| t.to_string()).unwrap_or_else(|| url.clone()),
+				url,
+				date: page_meta.date().map(|d| d.to_string()),
+				tags: page_meta.tags().into_iter().map(|t| t.to_string()).collect(),
+			});
+		}
+
+		match config.sort {
+			CollectionSort::Date => items.sort_by(|a, b| {
+				let (a_date, b_date) = (a.date.as_deref().and_then(parse_date), b.date.as_deref().and_then(parse_date));
+				// Note: Option orders None first, so, reversed for the most recent first and undated last.
+				b_date.cmp(&a_date).then_with(|| a.title.to_lowercase().cmp(&b.title.to_lowercase()))
+			}),
+			CollectionSort::Title => items.sort_by_key(|item| item.title.to_lowercase()),
+		}
+
+		Collection {
+			config: config.clone(),
+			index_url,
+			items,
+		}
+	}
+
+	/// The url of the 1 based list page (the first one is the collection dir index page, if any).
+	fn page_url(&self, site: &Site, page: usize) -> String {
+		match (page, self.index_url.as_ref()) {
+			(1, Some(index_url)) => index_url.to_string(),
+			_ => site
+				.dist_url(&virtual_dist_file(site, &f!("{}/page/{page}", self.config.dir)))
+				.unwrap_or_default(),
+		}
+	}
+}
+
+// region:    --- List Pages
+
+/// A generated list page of a collection, to be written.
+struct ListPageFile {
+	/// The collection content dir (for the frames).
+	dir: PathBuf,
+	dist_file: PathBuf,
+	values: Map<String, Value>,
+	list_page: ListPage,
+}
+
+/// Write the generated list pages (paginated lists and tag lists) of the site collections.
+/// The list pages that cannot be written are added to the report (with the collection dir as src).
+/// Returns the written files (for the dist file set).
+pub fn write_list_pages(site: &Site, report: &mut BuildReport) -> Vec<PathBuf> {
+	let mut files = Vec::new();
+
+	for ListPageFile { dir, dist_file, values, list_page } in list_page_files(site) {
+		match write_list_page(site, &dir, &dist_file, &values, &list_page) {
+			Ok(()) => files.push(dist_file),
+			Err(ex) => report.add_error(site, &dir, ex),
+		}
+	}
+
+	files
+}
+
+/// The dist files of the generated list pages (without writing them).
+pub fn list_page_dist_files(site: &Site) -> Vec<PathBuf> {
+	list_page_files(site).into_iter().map(|file| file.dist_file).collect()
+}
+
+fn list_page_files(site: &Site) -> Vec<ListPageFile> {
+	let collections = site.collections();
+	let mut files = Vec::new();
+
+	for collection in collections.collections.iter() {
+		let config = &collection.config;
+		let dir = site.content_dir().join(&config.dir);
+
+		// --- The paginated list pages (the first one only if no index page)
+		if config.per_page.is_some() {
+			let first = if collection.index_url.is_some() { 2 } else { 1 };
+			let total_pages = collection.total_pages();
+			for page in first..=total_pages {
+				files.push(ListPageFile {
+					dir: dir.clone(),
+					dist_file: virtual_dist_file(site, &f!("{}/page/{page}", config.dir)),
+					values: page_values(json!({ "title": f!("{} - Page {page}", config.title), "page_number": page })),
+					list_page: ListPage {
+						name: config.name.to_string(),
+						items: collection.page_items(page),
+						page,
+						total_pages,
+					},
+				});
+			}
+		}
+
+		// --- The tag list pages
+		if config.tags {
+			// tag slug -> tag (the first one, if many tags have the same slug)
+			// Note: The tags without slug (e.g., "++") are skipped, as their list page would be the `tags/` dir.
+			let mut tags: BTreeMap<String, &str> = BTreeMap::new();
+			for tag in collection.items.iter().flat_map(|i| i.tags.iter()) {
+				let slug = slugify(tag);
+				if slug.is_empty() {
+					println!(
+						"WARNING - tag '{tag}' of the {} collection has no url friendly name, so, no list page",
+						config.name
+					);
+					continue;
+				}
+				tags.entry(slug).or_insert(tag);
+			}
+			for (slug, tag) in tags {
+				files.push(ListPageFile {
+					dir: dir.clone(),
+					dist_file: virtual_dist_file(site, &f!("{}/tags/{slug}", config.dir)),
+					values: page_values(json!({ "title": f!("{} - {tag}", config.title), "tag": tag })),
+					list_page: ListPage {
+						name: config.name.to_string(),
+						items: collection.items.iter().filter(|i| i.tags.iter().any(|t| slugify(t) == slug)).cloned().collect(),
+						page: 1,
+						total_pages: 1,
+					},
+				});
+			}
+		}
+	}
+
+	files
+}
+
+fn write_list_page(site: &Site, dir: &Path, dist_file: &Path, values: &Map<String, Value>, list_page: &ListPage) -> Result<()> {
+	let content = f!("{INCLUDE_LIST}({})", list_page.name);
+	let content = render_virtual_page(site, dir, dist_file, values, &content, list_page)?;
+	if let Some(dist_dir) = dist_file.parent() {
+		fs::create_dir_all(dist_dir)?;
+	}
+	fs::write(dist_file, content)?;
+	Ok(())
+}
+
+// endregion: --- List Pages
+
+// region:    --- Include Lists
+
+/// Replace the `INCLUDE_LIST(name)` markers (and their eventual markdown `<p>` wrap) with the collection lists.
+/// The `list_page` items are used for its collection (i.e., generated list pages), otherwise, the first page items.
+pub fn include_lists(site: &Site, content: &str, list_page: Option<&ListPage>) -> String {
+	if !content.contains(INCLUDE_LIST) {
+		return s!(content);
+	}
+
+	let collections = site.collections();
+	INCLUDE_LIST_RX.replace_all(content, |caps: &Captures| {
+		let name = caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str()).unwrap_or_default();
+		let collection = match collections.get(name) {
+			Some(collection) => collection,
+			// Note: Unknown collections are replaced by an empty string (same as the slots).
+			None => return s!(""),
+		};
+
+		match list_page.filter(|lp| lp.name == name) {
+			Some(list_page) => list_html(site, collection, &list_page.items, list_page.page, list_page.total_pages),
+			None => list_html(site, collection, &collection.page_items(1), 1, collection.total_pages()),
+		}
+	})
+	.to_string()
+}
+
+fn list_html(site: &Site, collection: &Collection, items: &[CollectionItem], page: usize, total_pages: usize) -> String {
+	let mut html = s!("<ul class=\"ssite-list\">\n");
+	for item in items {
+		html.push_str(&f!("<li><a href=\"{}\">{}</a>", xml_escape(&item.url), xml_escape(&item.title)));
+		if let Some(date) = item.date.as_ref() {
+			let date = xml_escape(date);
+			html.push_str(&f!(" <time datetime=\"{date}\">{date}</time>"));
+		}
+		html.push_str("</li>\n");
+	}
+	html.push_str("</ul>");
+
+	if total_pages > 1 {
+		html.push_str("\n<nav class=\"ssite-pagination\">");
+		if page > 1 {
+			let url = collection.page_url(site, page - 1);
+			html.push_str(&f!("<a href=\"{}\" rel=\"prev\">Previous</a> ", xml_escape(&url)));
+		}
+		html.push_str(&f!("<span>Page {page} of {total_pages}</span>"));
+		if page < total_pages {
+			let url = collection.page_url(site, page + 1);
+			html.push_str(&f!(" <a href=\"{}\" rel=\"next\">Next</a>", xml_escape(&url)));
+		}
+		html.push_str("</nav>");
+	}
+
+	html
+}
+
+// endregion: --- Include Lists
+
+// region:    --- Utils
+
+fn page_values(values: Value) -> Map<String, Value> {
+	match values {
+		Value::Object(values) => values,
+		_ => Map::new(),
+	}
+}
+
+/// Url friendly name of a tag (e.g., "Rust Lang" is "rust-lang"), empty if none of its characters are alphanumeric.
+pub fn slugify(value: &str) -> String {
+	let mut slug = String::with_capacity(value.len());
+	for c in value.trim().chars().flat_map(|c| c.to_lowercase()) {
+		if c.is_alphanumeric() {
+			slug.push(c);
+		} else if !slug.ends_with('-') {
+			slug.push('-');
+		}
+	}
+	slug.trim_matches('-').to_string()
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_collections.rs"]
+mod tests;
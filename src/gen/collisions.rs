@@ -0,0 +1,130 @@
+//! Output collisions, i.e., content files with the same dist file (e.g., `hello.md` and `hello.html` are both
+//! `_site/hello` with the extensionless url style, and `README.md` and `index.html` are both `index.html`).
+//! They fail the build, unless the `[source] on_collision` gives the precedence (html or markdown).
+//! The generated files (e.g., `sitemap.xml`) always have the precedence over the content files (with a warning).
+
+use super::collections::list_page_dist_files;
+use super::feed::feed_dist_files;
+use super::processor::{removed_dist_file, FileProcessor};
+use super::sitemap::sitemap_dist_files;
+use crate::prelude::*;
+use crate::site::{OnCollision, Site};
+use crate::utils::lower_case;
+use pathdiff::diff_paths;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Collision {
+	pub dist_file: PathBuf,
+	/// The colliding src files (sorted).
+	pub src_files: Vec<PathBuf>,
+}
+
+impl Collision {
+	/// The src file kept per the `[source] on_collision`.
+	/// None for "error", or if not exactly one of the src files has the precedence.
+	pub fn winner(&self, on_collision: OnCollision) -> Option<&Path> {
+		let ext = match on_collision {
+			OnCollision::Error => return None,
+			OnCollision::Html => "html",
+			OnCollision::Markdown => "md",
+		};
+
+		let mut winners = self
+			.src_files
+			.iter()
+			.filter(|file| lower_case(file.extension()).as_deref() == Some(ext));
+		match (winners.next(), winners.next()) {
+			(Some(winner), None) => Some(winner),
+			_ => None,
+		}
+	}
+
+	/// The build error, with the colliding files named (relative to the root dir).
+	pub fn error(&self, site: &Site) -> Error {
+		let rel = |path: &Path| diff_paths(path, site.root_dir()).unwrap_or_else(|| path.to_path_buf());
+		let src_files: Vec<String> = self.src_files.iter().map(|f| rel(f).display().to_string()).collect();
+		Error::OutputCollision(rel(&self.dist_file).display().to_string(), src_files.join(" and "))
+	}
+}
+
+/// The collisions of the processors (sorted by dist file).
+pub fn find_collisions<'a>(processors: impl Iterator<Item = &'a FileProcessor>) -> Vec<Collision> {
+	let mut by_dist_file: BTreeMap<&Path, Vec<PathBuf>> = BTreeMap::new();
+	for processor in processors {
+		by_dist_file
+			.entry(processor.dist_file())
+			.or_default()
+			.push(processor.src_file().to_path_buf());
+	}
+
+	by_dist_file
+		.into_iter()
+		.filter(|(_, src_files)| src_files.len() > 1)
+		.map(|(dist_file, mut src_files)| {
+			src_files.sort();
+			Collision {
+				dist_file: dist_file.to_path_buf(),
+				src_files,
+			}
+		})
+		.collect()
+}
+
+/// The collision of the processor with the other (published) content files, if any (for the watch).
+pub fn find_collision(site: &Site, processor: &FileProcessor) -> Option<Collision> {
+	let others = dist_file_sources(site, processor.dist_file(), processor.src_file());
+	find_collisions(std::iter::once(processor).chain(others.iter())).pop()
+}
+
+/// The content file to process again for the dist file of a removed src file, if any (for the watch),
+/// i.e., the losing file of a collision the removed file was part of.
+/// With many remaining files, the one with the precedence (or, if none, the first one, with a warning).
+pub fn find_remaining_source(site: &Site, src_file: &Path) -> Option<FileProcessor> {
+	let dist_file = removed_dist_file(site, src_file)?;
+	let mut others = dist_file_sources(site, &dist_file, src_file);
+
+	let src_file = match find_collisions(others.iter()).pop() {
+		None => return others.pop(),
+		Some(collision) => match collision.winner(site.on_collision()) {
+			Some(winner) => winner.to_path_buf(),
+			None => {
+				println!("WARNING - {}", collision.error(site));
+				collision.src_files[0].to_path_buf()
+			}
+		},
+	};
+	others.into_iter().find(|other| other.src_file() == src_file)
+}
+
+/// The (published) content files with the dist file, except the `src_file`.
+fn dist_file_sources(site: &Site, dist_file: &Path, src_file: &Path) -> Vec<FileProcessor> {
+	site.content_entries()
+		.filter(|entry| entry.path() != src_file)
+		.filter_map(|entry| FileProcessor::from_src_file(site, entry.path().to_path_buf()))
+		.filter(|other| other.dist_file() == dist_file && other.is_published(site).unwrap_or(true))
+		.collect()
+}
+
+/// The dist files generated from the config and the collections (sitemap, robots, feeds, and list pages).
+pub fn generated_dist_files(site: &Site) -> BTreeSet<PathBuf> {
+	let mut files: BTreeSet<PathBuf> = sitemap_dist_files(site).into_iter().collect();
+	files.extend(feed_dist_files(site));
+	files.extend(list_page_dist_files(site));
+	files
+}
+
+/// The warning for a content file with the dist file of a generated file (the generated one wins).
+pub fn generated_collision_warning(site: &Site, src_file: &Path, dist_file: &Path) -> String {
+	let rel = |path: &Path| diff_paths(path, site.root_dir()).unwrap_or_else(|| path.to_path_buf());
+	f!(
+		"WARNING - {} has the same dist file as the generated {}, the generated file wins (the content file is skipped)",
+		rel(src_file).display(),
+		rel(dist_file).display()
+	)
+}
+
+#[cfg(test)]
+#[path = "../_tests/tests_collisions.rs"]
+mod tests;
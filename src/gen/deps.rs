@@ -0,0 +1,42 @@
+//! Dependency graph of the rendered pages (page -> the frames and data files it is rendered with).
+//!
+//! Built during the full generation (in memory), and updated in watch mode, so that a frame or data file change
+//! re-renders exactly the pages using it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub struct DepGraph {
+	/// page src file -> dependency files (e.g., page frame, directory frames, data files)
+	deps: BTreeMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl DepGraph {
+	pub fn set_deps(&mut self, page: &Path, deps: Vec<PathBuf>) {
+		self.deps.insert(page.to_path_buf(), deps);
+	}
+
+	pub fn remove_page(&mut self, page: &Path) {
+		self.deps.remove(page);
+	}
+
+	pub fn pages(&self) -> impl Iterator<Item = &PathBuf> {
+		self.deps.keys()
+	}
+
+	/// Return the pages depending on the given file (sorted).
+	pub fn dependents(&self, dep: &Path) -> Vec<PathBuf> {
+		self.deps
+			.iter()
+			.filter(|(_, deps)| deps.iter().any(|d| d == dep))
+			.map(|(page, _)| page.to_path_buf())
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.collect()
+	}
+}
+
+#[cfg(test)]
+#[path = "../_tests/tests_deps.rs"]
+mod tests;
@@ -0,0 +1,157 @@
+//! Explanation of the rendering of a content file (see `ssite explain`), i.e., its type, output, and how its frames
+//! are resolved (the frame files checked, why the lookup stopped, and the render order).
+
+use super::processor::{src_type_name, FileProcessor, FrameCheck, FrameKind, FramesStop};
+use crate::prelude::*;
+use crate::site::Site;
+use pathdiff::diff_paths;
+use std::env;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub struct Explanation {
+	/// src file, relative to the root dir.
+	pub src_file: PathBuf,
+	pub src_type: &'static str,
+	/// dist file, relative to the root dir (none for the frames).
+	pub dist_file: Option<PathBuf>,
+	pub url: Option<String>,
+	pub published: bool,
+	/// The frame files checked, in the lookup order (relative to the root dir). Empty if not a page.
+	pub checks: Vec<FrameCheck>,
+	/// None if not a page.
+	pub stop: Option<FramesStop>,
+	/// The frames, in the render order (relative to the root dir).
+	pub frames: Vec<PathBuf>,
+	/// The page html, then the html after each frame (only if asked for).
+	pub html_steps: Vec<String>,
+}
+
+/// Explain the rendering of a content file (relative to the current dir or the root dir).
+/// When `with_html`, the intermediate html is rendered as well (the page, then after each frame).
+pub fn explain(site: &Site, file: &Path, with_html: bool) -> Result<Explanation> {
+	let src_file = content_file(site, file)?;
+	let rel = |path: &Path| diff_paths(path, site.root_dir()).unwrap_or_else(|| path.to_path_buf());
+
+	let mut explanation = Explanation {
+		src_file: rel(&src_file),
+		src_type: src_type_name(&src_file),
+		dist_file: None,
+		url: None,
+		published: true,
+		checks: Vec::new(),
+		stop: None,
+		frames: Vec::new(),
+		html_steps: Vec::new(),
+	};
+
+	let processor = match FileProcessor::from_src_file(site, src_file) {
+		Some(processor) => processor,
+		// e.g., a frame
+		None => return Ok(explanation),
+	};
+	explanation.dist_file = Some(rel(processor.dist_file()));
+	explanation.url = site.dist_url(processor.dist_file());
+	explanation.published = processor.is_published(site)?;
+
+	if processor.is_for_html_render() {
+		let resolution = processor.resolve_frames(site)?;
+		explanation.checks = resolution
+			.checks
+			.into_iter()
+			.map(|check| FrameCheck {
+				file: rel(&check.file),
+				..check
+			})
+			.collect();
+		explanation.stop = Some(resolution.stop);
+		explanation.frames = resolution.frames.iter().map(|f| rel(f)).collect();
+
+		if with_html {
+			explanation.html_steps = processor.render_frame_steps(site)?;
+		}
+	}
+
+	Ok(explanation)
+}
+
+impl Display for Explanation {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		writeln!(f, "Explain - {} ({})", self.src_file.display(), self.src_type)?;
+		match (&self.dist_file, &self.url) {
+			(Some(dist_file), Some(url)) => write!(f, "  output: {} ({url})", dist_file.display())?,
+			_ => write!(f, "  output: none (frames are only rendered with their pages)")?,
+		}
+		if !self.published {
+			write!(f, "\n  unpublished: draft or future page (only rendered in dev)")?;
+		}
+
+		let stop = match &self.stop {
+			Some(stop) => stop,
+			// not a page, so, no frames
+			None => return Ok(()),
+		};
+
+		writeln!(f, "\n\nFrame lookup:")?;
+		let width = self.checks.iter().map(|c| c.file.display().to_string().len()).max().unwrap_or(0);
+		for check in self.checks.iter() {
+			let kind = match check.kind {
+				FrameKind::Page => "page frame",
+				FrameKind::Dir => "dir frame ",
+			};
+			let status = match (check.found, check.doctype) {
+				(true, true) => "found (<!DOCTYPE html>)",
+				(true, false) => "found",
+				(false, _) => "not found",
+			};
+			writeln!(f, "  - {kind}  {:<width$}  {status}", check.file.display())?;
+		}
+		match stop {
+			FramesStop::DoctypePage => writeln!(f, "  stop: the page starts with <!DOCTYPE html> (no frames)")?,
+			FramesStop::ContentDir => writeln!(f, "  stop: reached the content dir")?,
+		}
+
+		write!(f, "\nRender order:")?;
+		for (idx, file) in self.render_order().iter().enumerate() {
+			write!(f, "\n  {}. {file}", idx + 1)?;
+		}
+
+		for (step, html) in self.render_order().iter().zip(self.html_steps.iter()) {
+			write!(f, "\n\n--- html after {step}\n{}", html.trim_end())?;
+		}
+
+		Ok(())
+	}
+}
+
+impl Explanation {
+	/// The page, then its frames (as displayed).
+	fn render_order(&self) -> Vec<String> {
+		std::iter::once(f!("{} (page)", self.src_file.display()))
+			.chain(self.frames.iter().map(|frame| s!(frame.display())))
+			.collect()
+	}
+}
+
+/// The content file of the path (relative to the current dir, or else, to the root dir).
+fn content_file(site: &Site, file: &Path) -> Result<PathBuf> {
+	let candidates = [env::current_dir()?.join(file), site.root_dir().join(file)];
+	let src_file = candidates
+		.iter()
+		.find_map(|file| file.canonicalize().ok().filter(|file| file.is_file()))
+		.ok_or_else(|| Error::NotContentFile(file.display().to_string()))?;
+
+	if !src_file.starts_with(site.content_dir())
+		|| !site.valid_content_path(&src_file)
+		|| !site.is_included_path(&src_file, false)
+	{
+		return Err(Error::NotContentFile(file.display().to_string()));
+	}
+
+	Ok(src_file)
+}
+
+#[cfg(test)]
+#[path = "../_tests/tests_explain.rs"]
+mod tests;
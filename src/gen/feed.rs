@@ -0,0 +1,276 @@
+//! Atom and RSS feeds of the dated pages of a content dir (see the `[[feed]]` config sections).
+//!
+//! The entries are the published pages of the feed dir with a `date` front matter (`title`, `summary`, and `author` optional),
+//! with their rendered html body (without frames), most recent first.
+//! The pages that cannot be entries (e.g., invalid date, template error) are reported, and left out of the feed.
+
+use super::front_matter::{parse_date, PageMeta};
+use super::processor::FileProcessor;
+use super::report::BuildReport;
+use crate::prelude::*;
+use crate::site::{FeedConfig, Site};
+use crate::utils::xml_escape;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use time::format_description::well_known::{Rfc2822, Rfc3339};
+use time::OffsetDateTime;
+
+#[derive(Debug)]
+pub struct FeedEntry {
+	pub title: String,
+	/// root relative url (e.g., "/blog/hello")
+	pub url: String,
+	pub date: OffsetDateTime,
+	pub summary: Option<String>,
+	pub author: Option<String>,
+	/// rendered html body
+	pub content: String,
+}
+
+/// The rendered html bodies of the feed entries, by src file, so that the unchanged pages are not rendered again
+/// (e.g., on each watch batch). A body is valid until the dist file of its page is written again.
+#[derive(Debug, Default)]
+pub struct FeedBodies {
+	bodies: HashMap<PathBuf, FeedBody>,
+}
+
+#[derive(Debug)]
+struct FeedBody {
+	dist_modified: SystemTime,
+	page_meta: PageMeta,
+	content: String,
+}
+
+impl FeedBodies {
+	/// The page meta and html body of the page (rendered, if not cached or not valid anymore).
+	fn get(&mut self, site: &Site, processor: &FileProcessor) -> Result<(PageMeta, String)> {
+		let dist_modified = fs::metadata(processor.dist_file()).and_then(|m| m.modified()).ok();
+
+		if let (Some(body), Some(dist_modified)) = (self.bodies.get(processor.src_file()), dist_modified) {
+			if body.dist_modified == dist_modified {
+				return Ok((body.page_meta.clone(), body.content.clone()));
+			}
+		}
+
+		let (page_meta, content) = processor.render_body(site)?;
+		match dist_modified {
+			Some(dist_modified) => {
+				let body = FeedBody {
+					dist_modified,
+					page_meta: page_meta.clone(),
+					content: content.clone(),
+				};
+				self.bodies.insert(processor.src_file().to_path_buf(), body);
+			}
+			// Note: Not cached, as it could not be invalidated (e.g., not generated yet).
+			None => {
+				self.bodies.remove(processor.src_file());
+			}
+		}
+
+		Ok((page_meta, content))
+	}
+}
+
+/// Write the feeds of the `[[feed]]` configs. The pages that cannot be entries are added to the report.
+/// Returns the written files (for the dist file set).
+pub fn write_feeds(site: &Site, bodies: &mut FeedBodies, report: &mut BuildReport) -> Result<Vec<PathBuf>> {
+	let mut files = Vec::new();
+
+	for config in site.feed_configs() {
+		let entries = feed_entries(site, config, bodies, report);
+
+		if let Some(atom) = config.atom.as_ref() {
+			let file = site.dist_dir().join(atom);
+			let feed_url = site.dist_url(&file).unwrap_or_default();
+			write_file(&file, &atom_xml(config, &feed_url, &entries))?;
+			files.push(file);
+		}
+
+		if let Some(rss) = config.rss.as_ref() {
+			let file = site.dist_dir().join(rss);
+			let feed_url = site.dist_url(&file).unwrap_or_default();
+			write_file(&file, &rss_xml(config, &feed_url, &entries))?;
+			files.push(file);
+		}
+	}
+
+	Ok(files)
+}
+
+/// The dist files of the feeds (without writing them).
+pub fn feed_dist_files(site: &Site) -> Vec<PathBuf> {
+	site.feed_configs()
+		.iter()
+		.flat_map(|config| config.atom.iter().chain(config.rss.iter()))
+		.map(|file| site.dist_dir().join(file))
+		.collect()
+}
+
+/// The feed entries, most recent first, limited to the config limit.
+/// The pages that cannot be entries are added to the report (unless already reported, e.g., by their processing).
+fn feed_entries(site: &Site, config: &FeedConfig, bodies: &mut FeedBodies, report: &mut BuildReport) -> Vec<FeedEntry> {
+	let feed_dir = site.content_dir().join(&config.dir);
+
+	let mut entries: Vec<FeedEntry> = Vec::new();
+
+	for entry in site.content_entries().filter(|e| e.path().starts_with(&feed_dir)) {
+		let src_file = entry.path();
+		let processor = match FileProcessor::from_src_file(site, src_file.to_path_buf()) {
+			Some(processor) if processor.is_for_html_render() => processor,
+			_ => continue,
+		};
+
+		let entry = match feed_entry(site, &processor, bodies) {
+			Ok(Some(entry)) => entry,
+			Ok(None) => continue,
+			Err(ex) => {
+				if !report.has_error(site, src_file) {
+					report.add_error(site, src_file, ex);
+				}
+				continue;
+			}
+		};
+		entries.push(entry);
+	}
+
+	// most recent first (and by url for the same date, to be deterministic)
+	entries.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.url.cmp(&b.url)));
+	entries.truncate(config.limit);
+
+	entries
+}
+
+/// The feed entry of the page, if published and dated.
+fn feed_entry(site: &Site, processor: &FileProcessor, bodies: &mut FeedBodies) -> Result<Option<FeedEntry>> {
+	if !processor.is_published(site)? {
+		return Ok(None);
+	}
+
+	let (page_meta, content) = bodies.get(site, processor)?;
+
+	// Note: The undated pages (e.g., the index of the dir) are not entries.
+	let date = match page_meta.date() {
+		Some(date) => parse_date(date).ok_or_else(|| Error::InvalidFrontMatter(f!("invalid date '{date}'")))?,
+		None => return Ok(None),
+	};
+
+	let url = match site.dist_url(processor.dist_file()) {
+		Some(url) => url,
+		None => return Ok(None),
+	};
+
+	Ok(Some(FeedEntry {
+		title: page_meta.title().map(|t| t.to_string()).unwrap_or_else(|| url.clone()),
+		url,
+		date,
+		summary: page_meta.get("summary").and_then(|v| v.as_str()).map(|v| v.to_string()),
+		author: page_meta.get("author").and_then(|v| v.as_str()).map(|v| v.to_string()),
+		content,
+	}))
+}
+
+pub fn atom_xml(config: &FeedConfig, feed_url: &str, entries: &[FeedEntry]) -> String {
+	let base_url = &config.base_url;
+	let updated = entries.first().map(|e| e.date).unwrap_or(OffsetDateTime::UNIX_EPOCH);
+
+	let mut xml = s!(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+	xml.push_str("\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+	xml.push_str(&f!("  <title>{}</title>\n", xml_escape(&config.title)));
+	if let Some(description) = config.description.as_ref() {
+		xml.push_str(&f!("  <subtitle>{}</subtitle>\n", xml_escape(description)));
+	}
+	xml.push_str(&f!("  <link href=\"{}\" rel=\"self\"/>\n", xml_escape(&f!("{base_url}{feed_url}"))));
+	xml.push_str(&f!("  <link href=\"{}\"/>\n", xml_escape(&f!("{base_url}/{}/", config.dir))));
+	xml.push_str(&f!("  <id>{}</id>\n", xml_escape(&f!("{base_url}{feed_url}"))));
+	xml.push_str(&f!("  <updated>{}</updated>\n", rfc3339(updated)));
+	if let Some(author) = config.author.as_ref() {
+		xml.push_str(&f!("  <author><name>{}</name></author>\n", xml_escape(author)));
+	}
+
+	for entry in entries {
+		let url = xml_escape(&f!("{base_url}{}", entry.url));
+		xml.push_str("  <entry>\n");
+		xml.push_str(&f!("    <title>{}</title>\n", xml_escape(&entry.title)));
+		xml.push_str(&f!("    <link href=\"{url}\"/>\n"));
+		xml.push_str(&f!("    <id>{url}</id>\n"));
+		xml.push_str(&f!("    <updated>{}</updated>\n", rfc3339(entry.date)));
+		if let Some(author) = entry.author.as_ref() {
+			xml.push_str(&f!("    <author><name>{}</name></author>\n", xml_escape(author)));
+		}
+		if let Some(summary) = entry.summary.as_ref() {
+			xml.push_str(&f!("    <summary>{}</summary>\n", xml_escape(summary)));
+		}
+		xml.push_str(&f!("    <content type=\"html\">{}</content>\n", xml_escape(&entry.content)));
+		xml.push_str("  </entry>\n");
+	}
+
+	xml.push_str("</feed>\n");
+	xml
+}
+
+pub fn rss_xml(config: &FeedConfig, feed_url: &str, entries: &[FeedEntry]) -> String {
+	let base_url = &config.base_url;
+
+	let mut xml = s!(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+	xml.push_str("\n<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n");
+	xml.push_str("  <channel>\n");
+	xml.push_str(&f!("    <title>{}</title>\n", xml_escape(&config.title)));
+	xml.push_str(&f!("    <link>{}</link>\n", xml_escape(&f!("{base_url}/{}/", config.dir))));
+	xml.push_str(&f!(
+		"    <description>{}</description>\n",
+		xml_escape(config.description.as_deref().unwrap_or(&config.title))
+	));
+	xml.push_str(&f!(
+		"    <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+		xml_escape(&f!("{base_url}{feed_url}"))
+	));
+	if let Some(entry) = entries.first() {
+		xml.push_str(&f!("    <lastBuildDate>{}</lastBuildDate>\n", rfc2822(entry.date)));
+	}
+
+	for entry in entries {
+		let url = xml_escape(&f!("{base_url}{}", entry.url));
+		xml.push_str("    <item>\n");
+		xml.push_str(&f!("      <title>{}</title>\n", xml_escape(&entry.title)));
+		xml.push_str(&f!("      <link>{url}</link>\n"));
+		xml.push_str(&f!("      <guid>{url}</guid>\n"));
+		xml.push_str(&f!("      <pubDate>{}</pubDate>\n", rfc2822(entry.date)));
+		// Note: The RSS author must be an email, so, the dc:creator is used for the name.
+		if let Some(author) = entry.author.as_ref().or(config.author.as_ref()) {
+			xml.push_str(&f!("      <dc:creator>{}</dc:creator>\n", xml_escape(author)));
+		}
+		let description = entry.summary.as_ref().unwrap_or(&entry.content);
+		xml.push_str(&f!("      <description>{}</description>\n", xml_escape(description)));
+		xml.push_str("    </item>\n");
+	}
+
+	xml.push_str("  </channel>\n</rss>\n");
+	xml
+}
+
+// region:    --- Utils
+
+fn write_file(file: &Path, content: &str) -> Result<()> {
+	if let Some(dir) = file.parent() {
+		fs::create_dir_all(dir)?;
+	}
+	fs::write(file, content)?;
+	Ok(())
+}
+
+fn rfc3339(date: OffsetDateTime) -> String {
+	date.format(&Rfc3339).unwrap_or_default()
+}
+
+fn rfc2822(date: OffsetDateTime) -> String {
+	date.format(&Rfc2822).unwrap_or_default()
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_feed.rs"]
+mod tests;
@@ -0,0 +1,162 @@
+//! Front matter of the html and markdown pages.
+//!
+//! Supported formats (must be at the very start of the file):
+//! - YAML, between `---` lines
+//! - TOML, between `+++` lines
+
+use crate::prelude::*;
+use crate::utils::toml::toml_to_json;
+use crate::utils::yaml::yaml_to_json;
+use serde_json::{Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, PrimitiveDateTime};
+use yaml_rust::YamlLoader;
+
+const YAML_DELIM: &str = "---";
+const TOML_DELIM: &str = "+++";
+
+/// The per-page metadata from the front matter (title, date, draft, layout, slug, tags, and any other keys).
+#[derive(Debug, Default, Clone)]
+pub struct PageMeta {
+	values: Map<String, Value>,
+}
+
+/// Getters
+impl PageMeta {
+	pub fn get(&self, name: &str) -> Option<&Value> {
+		self.values.get(name)
+	}
+
+	pub fn title(&self) -> Option<&str> {
+		self.get_str("title")
+	}
+
+	pub fn date(&self) -> Option<&str> {
+		self.get_str("date")
+	}
+
+	pub fn draft(&self) -> bool {
+		self.get("draft").and_then(|v| v.as_bool()).unwrap_or(false)
+	}
+
+	pub fn publish_date(&self) -> Option<&str> {
+		self.get_str("publish_date")
+	}
+
+	/// The `template` flag, to render (or not) the page or frame with handlebars (overrides the `[source] template`).
+	pub fn template(&self) -> Option<bool> {
+		self.get("template").and_then(|v| v.as_bool())
+	}
+
+	pub fn tags(&self) -> Vec<&str> {
+		match self.get("tags") {
+			Some(Value::Array(tags)) => tags.iter().filter_map(|v| v.as_str()).collect(),
+			Some(Value::String(tag)) => vec![tag.as_str()],
+			_ => Vec::new(),
+		}
+	}
+
+	pub fn values(&self) -> &Map<String, Value> {
+		&self.values
+	}
+
+	fn get_str(&self, name: &str) -> Option<&str> {
+		self.get(name).and_then(|v| v.as_str())
+	}
+}
+
+impl PageMeta {
+	/// Determine if the page is published at `now`, i.e., not a `draft`, and no `publish_date` in the future.
+	pub fn is_published(&self, now: OffsetDateTime) -> Result<bool> {
+		if self.draft() {
+			return Ok(false);
+		}
+		match self.publish_date() {
+			Some(publish_date) => {
+				let publish_date = parse_date(publish_date)
+					.ok_or_else(|| Error::InvalidFrontMatter(f!("invalid publish_date '{publish_date}'")))?;
+				Ok(publish_date <= now)
+			}
+			None => Ok(true),
+		}
+	}
+}
+
+/// Split the eventual front matter from the content.
+/// Returns the page meta (empty if no front matter) and the content without the front matter.
+pub fn split_front_matter(content: &str) -> Result<(PageMeta, &str)> {
+	let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+
+	for delim in [YAML_DELIM, TOML_DELIM] {
+		if let Some((front, body)) = split_block(content, delim) {
+			let values = match delim {
+				YAML_DELIM => parse_yaml(front)?,
+				_ => parse_toml(front)?,
+			};
+			return Ok((PageMeta { values }, body));
+		}
+	}
+
+	Ok((PageMeta::default(), content))
+}
+
+/// Parse a front matter date, as RFC3339 (e.g., `2022-10-06T10:00:00Z`), local date time (`2022-10-06T10:00:00`, as UTC),
+/// or date (`2022-10-06`, at midnight UTC).
+pub fn parse_date(value: &str) -> Option<OffsetDateTime> {
+	let value = value.trim();
+	if let Ok(date_time) = OffsetDateTime::parse(value, &Rfc3339) {
+		return Some(date_time);
+	}
+	let date_time_format = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]");
+	if let Ok(date_time) = PrimitiveDateTime::parse(&value.replacen(' ', "T", 1), date_time_format) {
+		return Some(date_time.assume_utc());
+	}
+	Date::parse(value, format_description!("[year]-[month]-[day]"))
+		.ok()
+		.map(|date| date.midnight().assume_utc())
+}
+
+// region:    --- Utils
+
+/// Split the `delim` block at the start of the content. Returns None if the content does not start with it.
+fn split_block<'a>(content: &'a str, delim: &str) -> Option<(&'a str, &'a str)> {
+	let first_line_end = content.find('\n')?;
+	if content[..first_line_end].trim_end() != delim {
+		return None;
+	}
+
+	let rest = &content[first_line_end + 1..];
+	let mut offset = 0;
+	for line in rest.split_inclusive('\n') {
+		if line.trim_end() == delim {
+			return Some((&rest[..offset], &rest[offset + line.len()..]));
+		}
+		offset += line.len();
+	}
+
+	None
+}
+
+fn parse_yaml(front: &str) -> Result<Map<String, Value>> {
+	let docs = YamlLoader::load_from_str(front).map_err(|ex| Error::InvalidFrontMatter(s!(ex)))?;
+	match docs.into_iter().next().map(yaml_to_json) {
+		Some(Value::Object(values)) => Ok(values),
+		None | Some(Value::Null) => Ok(Map::new()),
+		Some(_) => Err(Error::InvalidFrontMatter(s!("YAML front matter must be a map"))),
+	}
+}
+
+fn parse_toml(front: &str) -> Result<Map<String, Value>> {
+	let toml: toml::Value = toml::from_str(front).map_err(|ex| Error::InvalidFrontMatter(s!(ex)))?;
+	match toml_to_json(toml) {
+		Value::Object(values) => Ok(values),
+		_ => Ok(Map::new()),
+	}
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_front_matter.rs"]
+mod tests;
@@ -1,67 +1,337 @@
+mod cache;
+mod collections;
+mod collisions;
+mod deps;
+mod explain;
+mod feed;
+mod front_matter;
 mod processor;
-mod runner;
+mod report;
+mod routes;
+mod sitemap;
+mod slots;
 
-use crate::consts::FRAME;
-use crate::site::Site;
+use crate::consts::{DIST_MARKER_FILE_NAME, SSITE_DIR};
+use crate::server::LiveReload;
+use crate::site::{Collections, Site};
+use crate::utils::DispStr;
 use crate::Error;
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use notify::RecursiveMode;
 use notify_debouncer_mini::{new_debouncer, DebouncedEvent, DebouncedEventKind};
-use std::collections::HashSet;
-use std::fs::{read_dir, remove_dir, remove_file};
+use pathdiff::diff_paths;
+use std::collections::{BTreeSet, HashSet};
+use std::fs::{self, read_dir, remove_dir, remove_dir_all, remove_file};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::channel;
+use std::thread;
 use std::time::Duration;
 use walkdir::WalkDir;
 
-use self::processor::FileProcessor;
+use self::cache::BuildCache;
+use self::collections::write_list_pages;
+use self::collisions::{
+	find_collision, find_collisions, find_remaining_source, generated_collision_warning, generated_dist_files,
+};
+use self::deps::DepGraph;
+use self::feed::{write_feeds, FeedBodies};
+use self::processor::is_frame_file;
+use self::sitemap::write_sitemap;
+pub use self::explain::explain;
+pub use self::processor::FileProcessor;
+pub use self::report::BuildReport;
+pub use self::routes::{routes, routes_table};
+
+/// Generate the site from the content dir to the dist dir.
+/// When `watch` is given, keep watching the content dir and notify the live reload of each regenerated file.
+pub async fn gen(site: &Site, watch: Option<&LiveReload>) -> Result<BuildReport, Error> {
+	let mut report = BuildReport::default();
+	let mut dep_graph = DepGraph::default();
+	let mut feed_bodies = FeedBodies::default();
+
+	// Note: Checked before the build, as the dist dir is not empty afterward.
+	let dist_marked = mark_dist_dir(site)?;
+
+	// load the collections (for the INCLUDE_LIST markers)
+	site.set_collections(Collections::load(site));
 
-pub async fn gen(site: &Site, watch: bool) -> Result<(), Error> {
 	// copy the content to site
-	let dst_set = copy_content_to_site(&site).await?;
+	let mut dst_set = copy_content_to_site(site, &mut report, &mut dep_graph).await?;
+
+	// generated files (after the content, so that they are not overridden by content files of the same name)
+	dst_set.extend(write_sitemap(site, &dep_graph)?);
+	dst_set.extend(write_feeds(site, &mut feed_bodies, &mut report)?);
+	let list_files = write_list_pages(site, &mut report);
+	dst_set.extend(list_files.iter().cloned());
 
 	// clean site dir (with thing that are not coming from content)
-	clean_site_dir(&site, &dst_set).await?;
+	if dist_marked {
+		report.removed = clean_site_dir(site, &dst_set).await?;
+	} else {
+		report.clean_skipped = true;
+		println!(
+			"WARNING - The dist dir {} is not empty and has no {DIST_MARKER_FILE_NAME} marker file, so, its stale files are not removed (add the marker file to enable the clean).",
+			site.dist_dir().display()
+		);
+	}
 
-	if watch {
-		watch_src_dir(&site).await?;
+	if let Some(live_reload) = watch {
+		// Note: The watch does not return, so, print the initial build report now.
+		println!("{report}");
+		let mut list_files: HashSet<PathBuf> = list_files.into_iter().collect();
+		watch_src_dir(site, &mut dep_graph, &mut feed_bodies, &mut list_files, live_reload).await?;
 	}
 
-	Ok(())
+	Ok(report)
 }
 
-async fn copy_content_to_site(site: &Site) -> Result<HashSet<PathBuf>, Error> {
+async fn copy_content_to_site(
+	site: &Site,
+	report: &mut BuildReport,
+	dep_graph: &mut DepGraph,
+) -> Result<HashSet<PathBuf>, Error> {
 	// the dst file set
 	let mut dst_set: HashSet<PathBuf> = HashSet::new();
+	let mut src_set: HashSet<PathBuf> = HashSet::new();
+	let mut cache = BuildCache::load(site);
+
+	// the files to output (published), and to process (the up to date ones are skipped)
+	let mut candidates: Vec<(FileProcessor, Vec<PathBuf>)> = Vec::new();
+	let mut processors: Vec<(FileProcessor, Vec<PathBuf>)> = Vec::new();
 
-	// copy and process the content files to _site/ dir
 	for entry in site.content_entries() {
-		if let Some(file_processor) = FileProcessor::from_src_file(site, entry.path().to_owned()) {
-			if let Ok(Some(dst_file)) = file_processor.process(site) {
+		let src_file = entry.path();
+		if let Some(file_processor) = FileProcessor::from_src_file(site, src_file.to_owned()) {
+			let deps = match file_processor.deps(site).and_then(|deps| Ok((deps, file_processor.is_published(site)?))) {
+				Ok((deps, true)) => deps,
+				// Note: Not added to the dst_set, so, the eventual previous output is removed by the clean_site_dir.
+				Ok((_, false)) => {
+					file_processor.log_unpublished(site);
+					report.unpublished += 1;
+					continue;
+				}
+				Err(ex) => {
+					file_processor.log_failed(site);
+					report.add_error(site, src_file, ex);
+					continue;
+				}
+			};
+			candidates.push((file_processor, deps));
+		}
+	}
+
+	// the files with the dist file of a generated file (e.g., `content/sitemap.xml` with a `[sitemap]` config)
+	// Note: The generated files are written after the content, so, they win.
+	let generated_files = generated_dist_files(site);
+	candidates.retain(|(file_processor, _)| {
+		let generated = generated_files.contains(file_processor.dist_file());
+		if generated {
+			println!(
+				"{}",
+				generated_collision_warning(site, file_processor.src_file(), file_processor.dist_file())
+			);
+		}
+		!generated
+	});
+
+	// the files with the same dist file (e.g., `hello.md` and `hello.html`), see `[source] on_collision`
+	// Note: Not added to the src_set, nor the dst_set, so that no file silently wins.
+	let mut skipped: HashSet<PathBuf> = HashSet::new();
+	for collision in find_collisions(candidates.iter().map(|(file_processor, _)| file_processor)) {
+		match collision.winner(site.on_collision()) {
+			Some(winner) => {
+				for src_file in collision.src_files.iter().filter(|src_file| *src_file != winner) {
+					println!(
+						"- skip (collision): {} ({} has the precedence)",
+						diff_paths(src_file, site.root_dir()).disp_str(),
+						diff_paths(winner, site.root_dir()).disp_str()
+					);
+					skipped.insert(src_file.to_path_buf());
+				}
+			}
+			None => {
+				report.add_error(site, &collision.src_files[0], collision.error(site));
+				skipped.extend(collision.src_files);
+			}
+		}
+	}
+
+	for (file_processor, deps) in candidates {
+		let src_file = file_processor.src_file();
+		if skipped.contains(src_file) {
+			continue;
+		}
+		src_set.insert(src_file.to_path_buf());
+
+		// skip if the dist file is up to date
+		// Note: The pages with lists depend on the other pages, so, they are always rendered.
+		if !file_processor.uses_lists(site).unwrap_or(true) && cache.is_fresh(src_file, &deps, file_processor.dist_file()) {
+			if file_processor.is_for_html_render() {
+				dep_graph.set_deps(src_file, deps);
+			}
+			dst_set.insert(file_processor.dist_file().to_path_buf());
+			report.unchanged += 1;
+			continue;
+		}
+
+		processors.push((file_processor, deps));
+	}
+
+	// process the files to the _site/ dir (in parallel)
+	// Note: block_in_place as the workers block on the file system.
+	let results = tokio::task::block_in_place(|| process_files(site, &processors));
+
+	for ((file_processor, deps), result) in processors.into_iter().zip(results) {
+		let src_file = file_processor.src_file();
+		match result {
+			Ok(Some(dst_file)) => {
+				if file_processor.is_for_html_render() {
+					report.rendered += 1;
+				} else {
+					report.copied += 1;
+				}
+				cache.update(src_file, &deps, &dst_file);
+				if file_processor.is_for_html_render() {
+					dep_graph.set_deps(src_file, deps);
+				}
 				dst_set.insert(dst_file);
 			}
+			Ok(None) => (),
+			Err(ex) => {
+				cache.remove(src_file);
+				report.add_error(site, src_file, ex);
+			}
 		}
 	}
 
+	cache.retain(&src_set);
+	cache.save(site)?;
+
 	Ok(dst_set)
 }
 
-async fn clean_site_dir(site: &Site, dst_set: &HashSet<PathBuf>) -> Result<(), Error> {
+/// Process the files with a bounded pool of worker threads (one per available core).
+/// The logs are printed, and the results returned, in the order of the processors.
+fn process_files(site: &Site, processors: &[(FileProcessor, Vec<PathBuf>)]) -> Vec<Result<Option<PathBuf>, Error>> {
+	let workers = thread::available_parallelism().map(|n| n.get()).unwrap_or(1).min(processors.len());
+	let next = AtomicUsize::new(0);
+	let (tx, rx) = channel();
+
+	let mut results: Vec<Option<Result<Option<PathBuf>, Error>>> = processors.iter().map(|_| None).collect();
+
+	thread::scope(|scope| {
+		for _ in 0..workers {
+			let tx = tx.clone();
+			let next = &next;
+			scope.spawn(move || loop {
+				let idx = next.fetch_add(1, Ordering::Relaxed);
+				let Some((file_processor, _)) = processors.get(idx) else {
+					break;
+				};
+				if tx.send((idx, file_processor.write_dist_file(site))).is_err() {
+					break;
+				}
+			});
+		}
+		drop(tx);
+
+		// Note: Print the logs as soon as all of the previous files are done, to keep the processors order.
+		let mut next_log = 0;
+		for (idx, result) in rx {
+			results[idx] = Some(result);
+			while let Some(Some(result)) = results.get(next_log) {
+				let (file_processor, _) = &processors[next_log];
+				match result {
+					Ok(Some(_)) => file_processor.log_processed(site),
+					Ok(None) => (),
+					// Note: The cause is printed in the build report summary.
+					Err(_) => file_processor.log_failed(site),
+				}
+				next_log += 1;
+			}
+		}
+	});
+
+	results.into_iter().map(|result| result.unwrap_or(Ok(None))).collect()
+}
+
+/// Remove all of the dist files (except the `[source] keep` ones), and the build cache.
+/// Fails if the dist dir is not empty and has no marker file (i.e., might not be generated by ssite).
+/// Returns the number of removed files.
+pub async fn clean(site: &Site) -> Result<usize, Error> {
+	if !mark_dist_dir(site)? {
+		return Err(Error::DistDirNotMarked(site.dist_dir().display().to_string()));
+	}
+
+	let removed = clean_site_dir(site, &HashSet::new()).await?;
+
+	// remove the empty dirs left (deepest first)
+	for entry in WalkDir::new(site.dist_dir())
+		.min_depth(1)
+		.contents_first(true)
+		.into_iter()
+		.filter_map(|e| e.ok().filter(|e| e.file_type().is_dir()))
+	{
+		if read_dir(entry.path())?.next().is_none() {
+			remove_dir(entry.path())?;
+		}
+	}
+
+	// the build cache and dependency graph are for the removed files
+	let ssite_dir = site.root_dir().join(SSITE_DIR);
+	if ssite_dir.is_dir() {
+		println!("--- Removing {}", ssite_dir.display());
+		remove_dir_all(ssite_dir)?;
+	}
+
+	Ok(removed)
+}
+
+/// Make sure the dist dir is marked as generated by ssite (writes the marker file if the dir is empty).
+/// Returns false if the dist dir is not empty and has no marker file.
+fn mark_dist_dir(site: &Site) -> Result<bool, Error> {
+	let marker_file = site.dist_dir().join(DIST_MARKER_FILE_NAME);
+	if marker_file.is_file() {
+		return Ok(true);
+	}
+	if read_dir(site.dist_dir())?.next().is_some() {
+		return Ok(false);
+	}
+	fs::write(
+		marker_file,
+		"Generated by ssite. The files of this dir not coming from the content dir are removed by the build.\n",
+	)?;
+	Ok(true)
+}
+
+/// Remove the dist files that are not part of the `dst_set` (except the marker file and the `[source] keep` files).
+/// Returns the number of removed files.
+async fn clean_site_dir(site: &Site, dst_set: &HashSet<PathBuf>) -> Result<usize, Error> {
 	let site_dir = site.dist_dir();
+	let marker_file = site_dir.join(DIST_MARKER_FILE_NAME);
+	let mut removed = 0;
 
-	for entry in WalkDir::new(&site_dir)
+	for entry in WalkDir::new(site_dir)
 		.into_iter()
 		.filter_map(|e| e.ok().filter(|f| f.path().is_file()))
 	{
 		let dst_file = entry.path();
-		if !dst_set.contains(dst_file) {
+		if !dst_set.contains(dst_file) && dst_file != marker_file && !site.is_kept_dist_file(dst_file) {
 			safer_remove_file_and_empty_parent(dst_file)?;
+			removed += 1;
 		}
 	}
 
-	Ok(())
+	Ok(removed)
 }
 
-async fn watch_src_dir(site: &Site) -> Result<(), Error> {
+async fn watch_src_dir(
+	site: &Site,
+	dep_graph: &mut DepGraph,
+	feed_bodies: &mut FeedBodies,
+	list_files: &mut HashSet<PathBuf>,
+	live_reload: &LiveReload,
+) -> Result<(), Error> {
 	let content_dir = site.content_dir();
 
 	// Create a channel to receive the events.
@@ -76,16 +346,41 @@ async fn watch_src_dir(site: &Site) -> Result<(), Error> {
 
 	// loop on rx
 	loop {
-		match rx.recv() {
+		// Note: block_in_place so that the other tasks (e.g., dev server) keep running while waiting on the events.
+		match tokio::task::block_in_place(|| rx.recv()) {
 			Ok(events) => match events {
 				Ok(events) => {
+					let mut dist_files: Vec<PathBuf> = Vec::new();
+					let mut report = BuildReport::default();
+					let mut frames_changed = false;
 					for DebouncedEvent { path, kind } in events.into_iter() {
 						// Note:  The other kind AnyContinuous is for timeout event, e.g., continuous write, which we do not want
 						// Note: We do not get another type of event now from notify, so just update. The handle_src_file_event will just know if the file is there or not.
 						if let DebouncedEventKind::Any = kind {
-							handle_src_file_event(site, path).await?
+							// skip the ignored files (the partials and data files are not part of the content, but are re-rendering the pages)
+							let is_template_input = path.starts_with(site.partials_dir()) || path.starts_with(site.data_dir());
+							if !is_template_input && !site.is_included_path(&path, path.is_dir()) {
+								continue;
+							}
+							frames_changed |= is_frame_file(&path) || path.starts_with(site.partials_dir());
+							match handle_src_file_event(site, dep_graph, &path, &mut report).await {
+								Ok(files) => dist_files.extend(files),
+								Err(ex) => report.add_error(site, &path, ex),
+							}
+						}
+					}
+					// Note: Pages might have been added or removed, so, update the generated files.
+					if !dist_files.is_empty() {
+						match update_generated_files(site, dep_graph, feed_bodies, &mut report, list_files, frames_changed) {
+							Ok(files) => dist_files.extend(files),
+							Err(ex) => println!("Error while updating the generated files\n  {ex}"),
 						}
 					}
+					// Note: In watch mode, the errors are reported, but the watch keeps going.
+					if report.has_failures() {
+						println!("{report}");
+					}
+					live_reload.notify(site.dist_dir(), &dist_files);
 				}
 
 				Err(err) => println!("ERROR on Debounced events\n {err:?}"),
@@ -96,37 +391,181 @@ async fn watch_src_dir(site: &Site) -> Result<(), Error> {
 }
 
 /// > Note: Unfortunately the Notify/FileSystem events are not really reliable, sometime get NotifyRemove or Remove when move, and no rename or even create.
-///         So, we have to deal with this by looking if the source file exists or not and do the appropriate acction
-async fn handle_src_file_event(site: &Site, src_file: PathBuf) -> Result<(), Error> {
+/// > So, we have to deal with this by looking if the source file exists or not and do the appropriate acction
+///
+/// Returns the dist files that were processed (or removed). The file processing errors are added to the report.
+async fn handle_src_file_event(
+	site: &Site,
+	dep_graph: &mut DepGraph,
+	src_file: &Path,
+	report: &mut BuildReport,
+) -> Result<Vec<PathBuf>, Error> {
+	let mut dist_files: Vec<PathBuf> = Vec::new();
+
 	// guard - do nothing if src_file belong to dist_dir
 	if src_file.starts_with(site.dist_dir()) {
-		return Ok(());
-	}
-
-	// if frame change, then, udpate all sub files
-	if src_file.ends_with(FRAME) {
-		if let Some(dir) = src_file.parent() {
-			for entry in WalkDir::new(&dir)
-				.into_iter()
-				.filter_map(|e| e.ok().filter(|f| f.path().is_file()))
-			{
-				let src_file = entry.path();
-				if let Some(processor) = FileProcessor::from_src_file(site, src_file.to_path_buf()) {
-					if processor.is_for_html_render() {
-						// TODO: Handle error
-						let _ = processor.process(site);
-					}
-				}
+		return Ok(dist_files);
+	}
+
+	// if partial change, then, reload the partials and update all pages
+	if src_file.starts_with(site.partials_dir()) {
+		site.templates().reload_partials()?;
+		let pages: Vec<PathBuf> = dep_graph.pages().cloned().collect();
+		dist_files.extend(render_pages(site, dep_graph, &pages, report));
+	}
+	// if data file change, then, reload the data and update the pages using it
+	else if src_file.starts_with(site.data_dir()) {
+		site.data().reload()?;
+		let pages = file_dependents(site, dep_graph, src_file, site.content_dir());
+		dist_files.extend(render_pages(site, dep_graph, &pages, report));
+	}
+	// if frame change (directory or page frame), then, update the pages using it
+	else if is_frame_file(src_file) {
+		let pages = match src_file.parent() {
+			Some(dir) => file_dependents(site, dep_graph, src_file, dir),
+			None => Vec::new(),
+		};
+		dist_files.extend(render_pages(site, dep_graph, &pages, report));
+	}
+	// otherwise, single file processing
+	else if let Some(file_processor) = FileProcessor::from_src_file(site, src_file.to_path_buf()) {
+		// Note: The generated files (e.g., sitemap.xml) win, as in the build.
+		if generated_dist_files(site).contains(file_processor.dist_file()) {
+			println!(
+				"{}",
+				generated_collision_warning(site, src_file, file_processor.dist_file())
+			);
+			return Ok(dist_files);
+		}
+		// Note: In watch mode, the collisions are only a warning (the losing file of the precedence is not processed).
+		if let Some(collision) = find_collision(site, &file_processor) {
+			let winner = collision.winner(site.on_collision());
+			println!("WARNING - {}", collision.error(site));
+			if winner.map(|winner| winner != src_file).unwrap_or(false) {
+				return Ok(dist_files);
+			}
+		}
+		dist_files.extend(process_file(site, dep_graph, &file_processor, report));
+	} else if !src_file.exists() {
+		dep_graph.remove_page(src_file);
+		// the other file of a collision the removed file was part of (e.g., the losing `hello.md` of `hello.html`)
+		if let Some(file_processor) = find_remaining_source(site, src_file) {
+			if !generated_dist_files(site).contains(file_processor.dist_file()) {
+				dist_files.extend(process_file(site, dep_graph, &file_processor, report));
 			}
 		}
 	}
-	// otherwise, single file processing
-	else if let Some(file_processor) = FileProcessor::from_src_file(site, src_file) {
-		// TODO: Handle error
-		let _ = file_processor.process(site);
+
+	Ok(dist_files)
+}
+
+/// Update the generated files after a watch batch (sitemap, feeds, and, if the collections changed, the list pages).
+/// The `list_files` are the generated list pages, to remove the ones not generated anymore.
+/// Returns the written (or removed) dist files.
+fn update_generated_files(
+	site: &Site,
+	dep_graph: &mut DepGraph,
+	feed_bodies: &mut FeedBodies,
+	report: &mut BuildReport,
+	list_files: &mut HashSet<PathBuf>,
+	frames_changed: bool,
+) -> Result<Vec<PathBuf>, Error> {
+	// Note: Loaded first, as the sitemap has the list pages.
+	let collections = Collections::load(site);
+	let collections_changed = *site.collections() != collections;
+	site.set_collections(collections);
+
+	let mut dist_files = write_sitemap(site, dep_graph)?;
+	// Note: Only the bodies of the re-rendered pages are rendered again (see FeedBodies).
+	dist_files.extend(write_feeds(site, feed_bodies, report)?);
+
+	if frames_changed || collections_changed {
+
+		// re-render the pages with lists
+		let pages: Vec<PathBuf> = dep_graph
+			.pages()
+			.filter(|page| {
+				FileProcessor::from_src_file(site, page.to_path_buf())
+					.map(|processor| processor.uses_lists(site).unwrap_or(false))
+					.unwrap_or(false)
+			})
+			.cloned()
+			.collect();
+		dist_files.extend(render_pages(site, dep_graph, &pages, report));
+
+		// re-generate the list pages, and remove the ones not generated anymore (e.g., tag removed)
+		let new_list_files: HashSet<PathBuf> = write_list_pages(site, report).into_iter().collect();
+		for stale_file in list_files.difference(&new_list_files) {
+			safer_remove_file_and_empty_parent(stale_file)?;
+			dist_files.push(stale_file.to_path_buf());
+		}
+		dist_files.extend(new_list_files.iter().cloned());
+		*list_files = new_list_files;
 	}
 
-	Ok(())
+	Ok(dist_files)
+}
+
+/// Re-render the pages, and update their dependencies.
+/// Returns the dist files of the rendered pages.
+fn render_pages(site: &Site, dep_graph: &mut DepGraph, pages: &[PathBuf], report: &mut BuildReport) -> Vec<PathBuf> {
+	let mut dist_files: Vec<PathBuf> = Vec::new();
+
+	for page in pages {
+		match FileProcessor::from_src_file(site, page.to_path_buf()) {
+			Some(processor) => dist_files.extend(process_file(site, dep_graph, &processor, report)),
+			// the page does not exist anymore
+			None => dep_graph.remove_page(page),
+		}
+	}
+
+	dist_files
+}
+
+/// Process a single file (watch mode), and update its dependencies.
+/// Returns the dist file if processed (or removed), otherwise, the error is added to the report.
+fn process_file(
+	site: &Site,
+	dep_graph: &mut DepGraph,
+	processor: &FileProcessor,
+	report: &mut BuildReport,
+) -> Option<PathBuf> {
+	let src_file = processor.src_file();
+	let res = processor.process(site).and_then(|dist_file| {
+		if processor.is_for_html_render() && dist_file.is_some() {
+			dep_graph.set_deps(src_file, processor.deps(site)?);
+			report.rendered += 1;
+		} else if dist_file.is_some() {
+			report.copied += 1;
+		}
+		Ok(processor.dist_file().to_path_buf())
+	});
+
+	match res {
+		Ok(dist_file) => Some(dist_file),
+		Err(ex) => {
+			report.add_error(site, src_file, ex);
+			None
+		}
+	}
+}
+
+/// Return the pages depending on the file (frame or data file).
+/// If no page depended on it (e.g., new file), the pages below the `pages_dir` are checked for it.
+fn file_dependents(site: &Site, dep_graph: &DepGraph, file: &Path, pages_dir: &Path) -> Vec<PathBuf> {
+	let mut pages: BTreeSet<PathBuf> = dep_graph.dependents(file).into_iter().collect();
+
+	if pages.is_empty() && file.is_file() {
+		for page in dep_graph.pages().filter(|page| page.starts_with(pages_dir)) {
+			if let Some(processor) = FileProcessor::from_src_file(site, page.to_path_buf()) {
+				if processor.deps(site).map(|deps| deps.iter().any(|d| d == file)).unwrap_or(false) {
+					pages.insert(page.to_path_buf());
+				}
+			}
+		}
+	}
+
+	pages.into_iter().collect()
 }
 
 // region:    Module Utils
@@ -152,3 +591,7 @@ fn safer_remove_file_and_empty_parent(file: &Path) -> Result<(), Error> {
 }
 
 // endregion: Module Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_gen.rs"]
+mod tests;
@@ -1,19 +1,25 @@
+use super::collections::{include_lists, ListPage};
+use super::front_matter::{split_front_matter, PageMeta};
 use super::safer_remove_file_and_empty_parent;
-use crate::consts::{FRAME, INCLUDE_CONTENT};
+use super::slots::{extract_slots, include_slots, unwrap_markdown_slots, Slots};
+use crate::consts::{FRAME, INCLUDE_CONTENT, INCLUDE_LIST};
 use crate::prelude::*;
-use crate::site::Site;
-use crate::utils::{lower_case, rebase_path, DispStr};
+use crate::site::{RunMode, Site, UrlStyle};
+use crate::utils::{has_url_scheme, lower_case, percent_decode, rebase_path, relative_url, DispStr};
 use crate::utils::{XStr, XString};
 use aho_corasick::AhoCorasick;
-use comrak::{markdown_to_html, ComrakOptions, ComrakRenderOptions};
+use comrak::nodes::NodeValue;
+use comrak::{format_html, markdown_to_html, parse_document, Arena, ComrakOptions, ComrakRenderOptions};
 use pathdiff::diff_paths;
-use std::fs::{self, create_dir_all, File};
-use std::io::{BufRead, BufReader};
+use serde_json::{json, Map, Value};
+use std::fs::{self, create_dir_all};
 use std::path::{Path, PathBuf};
+use strum_macros::IntoStaticStr;
+use time::OffsetDateTime;
 
 const DOC_TYPE: &str = "<!DOCTYPE html>";
 
-#[derive(Debug)]
+#[derive(Debug, IntoStaticStr)]
 enum SrcType {
 	FrameHtml,
 	FrameMarkdown,
@@ -46,10 +52,10 @@ impl SrcType {
 			} else if ext == "html" {
 				SrcType::PageHtml
 			} else {
-				return SrcType::Other;
+				SrcType::Other
 			}
 		} else {
-			return SrcType::Other;
+			SrcType::Other
 		}
 	}
 
@@ -67,17 +73,11 @@ impl SrcType {
 	}
 
 	fn is_frame(&self) -> bool {
-		match self {
-			SrcType::FrameHtml | SrcType::FrameMarkdown => true,
-			_ => false,
-		}
+		matches!(self, SrcType::FrameHtml | SrcType::FrameMarkdown)
 	}
 
 	fn is_markdown(&self) -> bool {
-		match self {
-			SrcType::FrameMarkdown | SrcType::PageMarkdown => true,
-			_ => false,
-		}
+		matches!(self, SrcType::FrameMarkdown | SrcType::PageMarkdown)
 	}
 }
 
@@ -96,22 +96,94 @@ impl FileProcessor {
 		// 	SrcType::Frame => None,
 		// 	_ => Some(get_dist_file(site, &src_type, &src_file)),
 		// };
-		match get_dist_file(site, &src_type, &src_file) {
-			Some(dist_file) => Some(FileProcessor {
-				src_file,
-				src_type,
-				dist_file,
-			}),
-			None => None,
-		}
+		get_dist_file(site, &src_type, &src_file).map(|dist_file| FileProcessor {
+			src_file,
+			src_type,
+			dist_file,
+		})
 	}
 
 	pub fn is_for_html_render(&self) -> bool {
 		self.src_type.is_for_html_render()
 	}
 
-	pub fn is_frame(&self) -> bool {
-		self.src_type.is_frame()
+	/// The files this page is rendered with (page frame, directory frames, and the data files used by them or by
+	/// their partials), and the content files linked by its markdown (see `markdown_page_to_html`),
+	/// for the dependency graph and the build cache.
+	pub fn deps(&self, site: &Site) -> Result<Vec<PathBuf>> {
+		if !self.is_for_html_render() {
+			return Ok(Vec::new());
+		}
+
+		let frames = self.get_frames(site)?;
+		let mut deps = frames.clone();
+		for target in self.content_link_targets(site)? {
+			if !deps.contains(&target) {
+				deps.push(target);
+			}
+		}
+		for file in std::iter::once(self.src_file.to_path_buf()).chain(frames) {
+			let content = fs::read_to_string(&file)?;
+			let partials = site.templates().used_partials(&content);
+			let mut contents = vec![content];
+			for partial in partials {
+				contents.push(fs::read_to_string(partial)?);
+			}
+			for data_file in contents.iter().flat_map(|content| site.data().used_files(content)) {
+				if !deps.contains(&data_file) {
+					deps.push(data_file);
+				}
+			}
+		}
+
+		Ok(deps)
+	}
+
+	/// Determine if the file is part of the site output for the site run mode.
+	/// In `Dev`, all files are. Otherwise, the draft pages and the pages with a future `publish_date` are not.
+	pub fn is_published(&self, site: &Site) -> Result<bool> {
+		if !self.is_for_html_render() || *site.run_mode() == RunMode::Dev {
+			return Ok(true);
+		}
+		self.page_meta()?.is_published(OffsetDateTime::now_utc())
+	}
+
+	/// The front matter of the page (empty if none).
+	pub fn page_meta(&self) -> Result<PageMeta> {
+		let content = fs::read_to_string(&self.src_file)?;
+		let (meta, _) = split_front_matter(&content)?;
+		Ok(meta)
+	}
+
+	/// Determine if the page is the index of its dir (`index.html`, `README.md`, or `index.md`).
+	pub fn is_index(&self) -> bool {
+		match self.src_type {
+			SrcType::IndexHtml | SrcType::ReadmeMarkdown => true,
+			SrcType::PageMarkdown => lower_case(self.src_file.file_stem()).as_deref() == Some("index"),
+			_ => false,
+		}
+	}
+
+	/// Determine if the page or its frames have `INCLUDE_LIST(..)` markers (i.e., depends on the collections).
+	pub fn uses_lists(&self, site: &Site) -> Result<bool> {
+		if !self.is_for_html_render() {
+			return Ok(false);
+		}
+		let marker = f!("{INCLUDE_LIST}(");
+		for file in std::iter::once(self.src_file.to_path_buf()).chain(self.get_frames(site)?) {
+			if fs::read_to_string(&file)?.contains(&marker) {
+				return Ok(true);
+			}
+		}
+		Ok(false)
+	}
+
+	pub fn src_file(&self) -> &Path {
+		&self.src_file
+	}
+
+	pub fn dist_file(&self) -> &Path {
+		&self.dist_file
 	}
 
 	pub fn root_rel_dist_file(&self, site: &Site) -> Option<PathBuf> {
@@ -126,6 +198,15 @@ impl FileProcessor {
 /// Processors
 impl FileProcessor {
 	pub fn process(&self, site: &Site) -> Result<Option<PathBuf>> {
+		let dist_file = self.write_dist_file(site)?;
+		if dist_file.is_some() {
+			self.log_processed(site);
+		}
+		Ok(dist_file)
+	}
+
+	/// Same as `process`, without the log (e.g., for the parallel build, which logs in order).
+	pub fn write_dist_file(&self, site: &Site) -> Result<Option<PathBuf>> {
 		// if the src file does not exist, then, we clean the dist file
 		if !self.src_file.exists() {
 			safer_remove_file_and_empty_parent(&self.dist_file)?;
@@ -143,25 +224,35 @@ impl FileProcessor {
 			// --- get the frames
 			// Call render, and if there is some content, we use the content.
 			// Otherwise, just copy the file
-			match self.render_content(site) {
-				Ok(Some(content)) => {
+			match self.render_content(site)? {
+				Some(content) => {
 					fs::write(&self.dist_file, content)?;
 				}
-				Ok(None) => {
+				None => {
 					fs::copy(&self.src_file, &self.dist_file)?;
 				}
-				Err(ex) => println!("Error while rendering file {:?}", ex),
 			}
-			println!(
-				"- process:    {:<40} >>    {}",
-				self.root_rel_src_file(site).disp_str(),
-				self.root_rel_dist_file(site).disp_str()
-			);
 
 			Ok(Some(self.dist_file.to_owned()))
 		}
 	}
 
+	pub fn log_processed(&self, site: &Site) {
+		println!(
+			"- process:    {:<40} >>    {}",
+			self.root_rel_src_file(site).disp_str(),
+			self.root_rel_dist_file(site).disp_str()
+		);
+	}
+
+	pub fn log_unpublished(&self, site: &Site) {
+		println!("- unpublished: {}", self.root_rel_src_file(site).disp_str());
+	}
+
+	pub fn log_failed(&self, site: &Site) {
+		println!("- FAILED:     {}", self.root_rel_src_file(site).disp_str());
+	}
+
 	/// Render the content as string.
 	/// Return None if the content does not need rendering (can be copied directly).
 	fn render_content(&self, site: &Site) -> Result<Option<String>> {
@@ -169,153 +260,445 @@ impl FileProcessor {
 			return Ok(None);
 		}
 
-		// --- Markdown options
-		let render_opts = ComrakRenderOptions {
-			unsafe_: true,
-			..Default::default()
-		};
-
-		let opts = ComrakOptions {
-			render: render_opts,
-			..Default::default()
-		};
-
 		// --- Get grames
 		let frames = self.get_frames(site)?;
 
 		// --- Render Page
-		let mut src_content = fs::read_to_string(&self.src_file)?;
+		let (_, data, slots, src_content) = self.render_page(site)?;
 
-		// If markdown, render html.
+		// --- Render the frames, and the lists
+		let content = apply_frames(site, &frames, &data, &slots, src_content)?;
+		let content = include_lists(site, &content, None);
+
+		Ok(Some(content))
+	}
+
+	/// Render the page html body (i.e., without its frames), for the feeds.
+	/// Returns the page meta and the html.
+	pub fn render_body(&self, site: &Site) -> Result<(PageMeta, String)> {
+		let (page_meta, _, _, content) = self.render_page(site)?;
+		Ok((page_meta, content))
+	}
+
+	/// Render the page (front matter, handlebars, slots, and markdown).
+	/// Returns the page meta, the template data (for the frames), the page slots, and the html content.
+	fn render_page(&self, site: &Site) -> Result<(PageMeta, Value, Slots, String)> {
+		let src_content = fs::read_to_string(&self.src_file)?;
+		let (page_meta, src_content) = split_front_matter(&src_content)?;
+		let data = self.template_data(site, &page_meta);
+		let src_content = match page_meta.template().unwrap_or(site.template()) {
+			true => site.templates().render(&self.src_file, src_content, &data)?,
+			false => src_content.to_string(),
+		};
+		let (slots, mut src_content) = extract_slots(&src_content);
+
+		// If markdown, render html (with the links to the content files rewritten to their urls).
 		if self.src_type.is_markdown() {
-			src_content = markdown_to_html(&src_content, &opts)
+			src_content = self.markdown_page_to_html(site, &src_content)?;
 		}
 
-		// TODO: Process content with handlebars.
+		Ok((page_meta, data, slots, src_content))
+	}
 
-		if frames.len() == 0 {
-			Ok(Some(src_content))
-		} else {
-			let include_content_ac_patterns = &[INCLUDE_CONTENT];
-			let include_content_p_clean_ac_patterns = &[f!("<p>{INCLUDE_CONTENT}</p>")];
-			let mut content = src_content;
-
-			for frame in frames.iter() {
-				let frame_type = SrcType::from_path(frame);
-				let mut frame_content = fs::read_to_string(frame)?;
-
-				// If markdown, render html.
-				if frame_type.is_markdown() {
-					frame_content = markdown_to_html(&frame_content, &opts);
-
-					// Note: Here if we have INCLUDE_CONTENT, it will render as <p>INCLUDE_CONTENT</p>,
-					//       and the p tags should be removed.
-					//       This is important, otherwise, all content will be wrapped in <p></p>.
-					let ac = AhoCorasick::new(include_content_p_clean_ac_patterns);
-					let res = ac.replace_all_bytes(frame_content.as_bytes(), &[INCLUDE_CONTENT]);
-					let rendered = std::str::from_utf8(&res).unwrap();
-					frame_content = rendered.to_string();
+	/// Render the markdown of the page, with the relative links to the content files (e.g., `../sub-dir/README.md`)
+	/// rewritten to the relative urls of their dist files (e.g., `../sub-dir/`).
+	fn markdown_page_to_html(&self, site: &Site, markdown: &str) -> Result<String> {
+		let opts = markdown_options();
+		let arena = Arena::new();
+		let root = parse_document(&arena, markdown, &opts);
+
+		for node in root.descendants() {
+			if let NodeValue::Link(ref mut link) = node.data.borrow_mut().value {
+				if let Some(url) = self.content_link_url(site, &String::from_utf8_lossy(&link.url)) {
+					link.url = url.into_bytes();
 				}
-
-				// Now, inlude the content.
-				let ac = AhoCorasick::new(include_content_ac_patterns);
-				let res = ac.replace_all_bytes(frame_content.as_bytes(), &[&content]);
-				let rendered = std::str::from_utf8(&res).unwrap();
-				content = rendered.to_string();
 			}
+		}
+
+		let mut html = Vec::new();
+		format_html(root, &opts, &mut html)?;
+		Ok(String::from_utf8_lossy(&html).to_string())
+	}
+
+	/// The relative url of the dist file of a relative link (None if not a link to a content file).
+	fn content_link_url(&self, site: &Site, link: &str) -> Option<String> {
+		let processor = self.content_link_target(site, link)?;
 
-			Ok(Some(content))
+		// the eventual query and anchor are kept as is
+		let suffix = &link[link.find(['?', '#']).unwrap_or(link.len())..];
+		let page_url = site.dist_url(&self.dist_file)?;
+		let target_url = site.dist_url(processor.dist_file())?;
+
+		Some(f!("{}{suffix}", relative_url(&page_url, &target_url)))
+	}
+
+	/// The processor of the content file of a relative link (None if not a link to a content file).
+	fn content_link_target(&self, site: &Site, link: &str) -> Option<FileProcessor> {
+		if link.is_empty() || link.starts_with(['/', '#']) || has_url_scheme(link) {
+			return None;
+		}
+
+		let path = &link[..link.find(['?', '#']).unwrap_or(link.len())];
+		let target = self.src_file.parent()?.join(percent_decode(path)).canonicalize().ok()?;
+		if !target.is_file() || !site.valid_content_path(&target) || !site.is_included_path(&target, false) {
+			return None;
 		}
+
+		FileProcessor::from_src_file(site, target)
 	}
 
-	fn get_frames(&self, site: &Site) -> Result<Vec<PathBuf>> {
-		let mut path = self.src_file.to_path_buf();
+	/// The content files linked by the markdown of the page (their urls are in the page html).
+	/// Note: The links are taken from the markdown source (i.e., before its handlebars render).
+	fn content_link_targets(&self, site: &Site) -> Result<Vec<PathBuf>> {
+		if !self.src_type.is_markdown() {
+			return Ok(Vec::new());
+		}
 
+		let content = fs::read_to_string(&self.src_file)?;
+		let (_, markdown) = split_front_matter(&content)?;
+		let arena = Arena::new();
+		let root = parse_document(&arena, markdown, &markdown_options());
+
+		let mut targets: Vec<PathBuf> = Vec::new();
+		for node in root.descendants() {
+			if let NodeValue::Link(ref link) = node.data.borrow().value {
+				if let Some(target) = self.content_link_target(site, &String::from_utf8_lossy(&link.url)) {
+					if !targets.iter().any(|t| t == target.src_file()) {
+						targets.push(target.src_file);
+					}
+				}
+			}
+		}
+
+		Ok(targets)
+	}
+
+	/// The handlebars data for the page and its frames.
+	fn template_data(&self, site: &Site, page_meta: &PageMeta) -> Value {
+		template_data(site, &self.dist_file, page_meta.values())
+	}
+
+	/// The frames the page is rendered with, from the closest (page frame) to the farthest (root directory frame).
+	pub fn get_frames(&self, site: &Site) -> Result<Vec<PathBuf>> {
+		Ok(self.resolve_frames(site)?.frames)
+	}
+
+	/// Resolve the frames of the page, with the frame files checked and why the lookup stopped (see `ssite explain`).
+	///
+	/// The page frame (`my-page_frame.html|md`) first, then the `_frame.html` of each dir up to the content dir.
+	/// A `<!DOCTYPE html>` page has no frames.
+	pub fn resolve_frames(&self, site: &Site) -> Result<FrameResolution> {
+		let mut checks: Vec<FrameCheck> = Vec::new();
 		let mut frames: Vec<PathBuf> = Vec::new();
 
 		// if this file is a doctype html, then, no frames.
-		if is_doctype_html(&path)? {
-			return Ok(frames);
+		if is_doctype_html(&self.src_file)? {
+			return Ok(FrameResolution {
+				checks,
+				frames,
+				stop: FramesStop::DoctypePage,
+			});
 		}
 
-		// first, check if we have page frame.
-		if let Some(page_frame) = get_page_frame_for_file(&path) {
-			frames.push(page_frame);
+		// first, check if we have page frame (the html one first).
+		for page_frame in page_frame_candidates(&self.src_file) {
+			let found = page_frame.is_file();
+			let doctype = found && is_doctype_html(&page_frame)?;
+			checks.push(FrameCheck {
+				file: page_frame.to_path_buf(),
+				kind: FrameKind::Page,
+				found,
+				doctype,
+			});
+			if found {
+				frames.push(page_frame.to_path_buf());
+				break;
+			}
 		}
 
 		// then, walk the path back.
+		let mut path = self.src_file.as_path();
 		while let Some(dir) = path.parent() {
 			let frame = dir.join(FRAME);
-			if frame.is_file() {
-				frames.push(frame.to_owned());
-				// if this frame is a doctype html, then, it's the last.
-				if is_doctype_html(&path)? {
-					break;
-				}
+			let found = frame.is_file();
+			let doctype = found && is_doctype_html(&frame)?;
+			checks.push(FrameCheck {
+				file: frame.to_path_buf(),
+				kind: FrameKind::Dir,
+				found,
+				doctype,
+			});
+			if found {
+				frames.push(frame.to_path_buf());
 			}
 			// if the dir is the content_dir, then, this the end of line.
 			if dir == site.content_dir() {
 				break;
 			}
-			path = dir.to_path_buf();
+			path = dir;
 		}
 
-		Ok(frames)
+		Ok(FrameResolution {
+			checks,
+			frames,
+			stop: FramesStop::ContentDir,
+		})
+	}
+
+	/// Render the page, then each of its frames in turn (for `ssite explain`).
+	/// Returns the page html, then the html after each frame is applied (before the list inclusion).
+	pub fn render_frame_steps(&self, site: &Site) -> Result<Vec<String>> {
+		let frames = self.get_frames(site)?;
+		let (_, data, slots, content) = self.render_page(site)?;
+
+		let mut steps = vec![content];
+		for frame in frames.iter() {
+			let content = apply_frames(site, std::slice::from_ref(frame), &data, &slots, steps[steps.len() - 1].clone())?;
+			steps.push(content);
+		}
+
+		Ok(steps)
+	}
+}
+
+// region:    --- Frame Resolution
+
+/// The frames of a page, with the lookup trace (see `FileProcessor::resolve_frames`).
+#[derive(Debug)]
+pub struct FrameResolution {
+	/// The frame files checked, in the lookup order.
+	pub checks: Vec<FrameCheck>,
+	/// The frames found, in the render order (from the closest to the farthest).
+	pub frames: Vec<PathBuf>,
+	pub stop: FramesStop,
+}
+
+#[derive(Debug)]
+pub struct FrameCheck {
+	pub file: PathBuf,
+	pub kind: FrameKind,
+	pub found: bool,
+	/// The frame starts with `<!DOCTYPE html>`.
+	pub doctype: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FrameKind {
+	/// `my-page_frame.html|md`
+	Page,
+	/// `_frame.html`
+	Dir,
+}
+
+/// Why the frame lookup stopped.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FramesStop {
+	/// The page starts with `<!DOCTYPE html>` (no frames).
+	DoctypePage,
+	/// The content dir frame was checked.
+	ContentDir,
+}
+
+// endregion: --- Frame Resolution
+
+// region:    --- Virtual Pages
+
+/// Render a generated page (e.g., the collection list pages) with the frames of its dir.
+/// The `content` is the html of the page (e.g., with `INCLUDE_LIST(name)` markers).
+pub fn render_virtual_page(
+	site: &Site,
+	dir: &Path,
+	dist_file: &Path,
+	page_values: &Map<String, Value>,
+	content: &str,
+	list_page: &ListPage,
+) -> Result<String> {
+	let frames = dir_frames(site, dir);
+	let data = template_data(site, dist_file, page_values);
+	let content = apply_frames(site, &frames, &data, &Slots::default(), s!(content))?;
+	Ok(include_lists(site, &content, Some(list_page)))
+}
+
+/// The dist file of a generated page, per the url style (e.g., "blog/page/2" is `_site/blog/page/2`).
+pub fn virtual_dist_file(site: &Site, rel_path: &str) -> PathBuf {
+	let dist_file = site.dist_dir().join(rel_path);
+	match site.url_style() {
+		UrlStyle::Extensionless => dist_file,
+		UrlStyle::Html => dist_file.with_extension("html"),
+		UrlStyle::Directory => dist_file.join("index.html"),
 	}
 }
 
+// endregion: --- Virtual Pages
+
 // region:    --- Utils
 
+/// The handlebars data for a page and its frames.
+fn template_data(site: &Site, dist_file: &Path, page_values: &Map<String, Value>) -> Value {
+	json!({
+		"site": site.site_values(),
+		"page": page_values,
+		"data": site.data().values(),
+		"page_url": site.dist_url(dist_file).unwrap_or_default(),
+		"root_path": root_path(site, dist_file),
+		"build_time": site.build_time(),
+	})
+}
+
+/// Render the frames (from the closest to the farthest), with the page slots, around the content.
+fn apply_frames(site: &Site, frames: &[PathBuf], data: &Value, slots: &Slots, content: String) -> Result<String> {
+	let opts = markdown_options();
+	let include_content_ac_patterns = &[INCLUDE_CONTENT];
+	let include_content_p_clean_ac_patterns = &[f!("<p>{INCLUDE_CONTENT}</p>")];
+	let mut content = content;
+
+	for frame in frames.iter() {
+		let frame_type = SrcType::from_path(frame);
+		let frame_content = fs::read_to_string(frame)?;
+		let (frame_meta, frame_content) = split_front_matter(&frame_content)?;
+		let mut frame_content = match frame_meta.template().unwrap_or(site.template()) {
+			true => site.templates().render(frame, frame_content, data)?,
+			false => frame_content.to_string(),
+		};
+
+		// If markdown, render html.
+		if frame_type.is_markdown() {
+			frame_content = markdown_to_html(&frame_content, &opts);
+
+			// Note: Here if we have INCLUDE_CONTENT, it will render as <p>INCLUDE_CONTENT</p>,
+			//       and the p tags should be removed.
+			//       This is important, otherwise, all content will be wrapped in <p></p>.
+			let ac = AhoCorasick::new(include_content_p_clean_ac_patterns);
+			let res = ac.replace_all_bytes(frame_content.as_bytes(), &[INCLUDE_CONTENT]);
+			let rendered = std::str::from_utf8(&res).unwrap();
+			frame_content = unwrap_markdown_slots(rendered);
+		}
+
+		// Include the page slots.
+		frame_content = include_slots(&frame_content, slots);
+
+		// Now, inlude the content.
+		let ac = AhoCorasick::new(include_content_ac_patterns);
+		let res = ac.replace_all_bytes(frame_content.as_bytes(), &[&content]);
+		let rendered = std::str::from_utf8(&res).unwrap();
+		content = rendered.to_string();
+	}
+
+	Ok(content)
+}
+
+/// The `_frame.html` of the dir and of each parent dir up to the content dir (closest first),
+/// as for the pages (see `FileProcessor::resolve_frames`).
+fn dir_frames(site: &Site, dir: &Path) -> Vec<PathBuf> {
+	let mut frames: Vec<PathBuf> = Vec::new();
+
+	for dir in dir.ancestors().take_while(|d| d.starts_with(site.content_dir())) {
+		let frame = dir.join(FRAME);
+		if frame.is_file() {
+			frames.push(frame);
+		}
+	}
+
+	frames
+}
+
+fn markdown_options() -> ComrakOptions {
+	let render_opts = ComrakRenderOptions {
+		unsafe_: true,
+		..Default::default()
+	};
+
+	ComrakOptions {
+		render: render_opts,
+		..Default::default()
+	}
+}
+
+/// The source type name of the file (e.g., "PageMarkdown", "FrameHtml", "Other").
+pub fn src_type_name(path: &Path) -> &'static str {
+	SrcType::from_path(path).into()
+}
+
+/// Determine if the file is a directory frame (`_frame.html`) or page frame (`my-page_frame.html|md`).
+pub fn is_frame_file(path: &Path) -> bool {
+	SrcType::from_path(path).is_frame()
+}
+
 fn get_dist_file(site: &Site, src_type: &SrcType, src_file: &Path) -> Option<PathBuf> {
 	// if not a file, return None.
 	if !src_file.is_file() {
 		return None;
 	}
 
+	src_dist_file(site, src_type, src_file)
+}
+
+/// The dist file of a content file, even if it does not exist anymore (e.g., removed in watch mode).
+/// None for the frames.
+pub fn removed_dist_file(site: &Site, src_file: &Path) -> Option<PathBuf> {
+	src_dist_file(site, &SrcType::from_path(src_file), src_file)
+}
+
+fn src_dist_file(site: &Site, src_type: &SrcType, src_file: &Path) -> Option<PathBuf> {
 	if src_type.is_frame() {
 		return None;
 	}
 
 	let (content_dir, site_dist_dir) = (site.content_dir(), site.dist_dir());
 
-	// for now, just return the result of rebase path
-	if let Some(mut dist_file) = rebase_path(content_dir, src_file, site_dist_dir) {
-		let new_file_name = match src_type {
-			SrcType::ReadmeMarkdown => Some("index.html".to_owned()),
-			SrcType::PageMarkdown | SrcType::PageHtml => dist_file.file_stem().x_string(),
-			_ => None,
-		};
-
-		match new_file_name {
-			Some(new_file_name) => {
-				dist_file.set_file_name(new_file_name);
-				Some(dist_file)
+	let mut dist_file = rebase_path(content_dir, src_file, site_dist_dir)?;
+
+	match src_type {
+		SrcType::ReadmeMarkdown => dist_file.set_file_name("index.html"),
+		SrcType::PageMarkdown | SrcType::PageHtml => {
+			let stem = dist_file.file_stem().x_string()?;
+			match site.url_style() {
+				// Note: `index.md` is the index of its dir (i.e., served as `/dir/`), whatever the url style.
+				_ if stem.eq_ignore_ascii_case("index") => dist_file.set_file_name("index.html"),
+				UrlStyle::Extensionless => dist_file.set_file_name(stem),
+				UrlStyle::Html => {
+					dist_file.set_extension("html");
+				}
+				UrlStyle::Directory => {
+					dist_file.set_file_name(stem);
+					dist_file.push("index.html");
+				}
 			}
-			None => Some(dist_file),
 		}
+		_ => (),
+	}
+
+	Some(dist_file)
+}
+
+/// The relative path from the dist file to the dist dir (e.g., "./" or "../../").
+fn root_path(site: &Site, dist_file: &Path) -> String {
+	let depth = dist_file
+		.strip_prefix(site.dist_dir())
+		.map(|rel| rel.components().count().saturating_sub(1))
+		.unwrap_or(0);
+
+	if depth == 0 {
+		s!("./")
 	} else {
-		None
+		"../".repeat(depth)
 	}
 }
 
-/// Return the eventual page_frame.html or .md if exists
-fn get_page_frame_for_file(file: &Path) -> Option<PathBuf> {
-	if let (Some(stem), Some(dir)) = (file.file_stem().x_str(), file.parent()) {
-		for ext in ["html", "md"] {
-			let page_frame = dir.join(f!("{stem}_frame.{ext}"));
-			if page_frame.is_file() {
-				return Some(page_frame);
-			}
-		}
+/// The page frame files of a page, in the lookup order (e.g., `my-page_frame.html`, then `my-page_frame.md`).
+fn page_frame_candidates(file: &Path) -> Vec<PathBuf> {
+	match (file.file_stem().x_str(), file.parent()) {
+		(Some(stem), Some(dir)) => ["html", "md"].iter().map(|ext| dir.join(f!("{stem}_frame.{ext}"))).collect(),
+		_ => Vec::new(),
 	}
-	None
 }
 
+/// Determine if the first line of the file (after the eventual front matter) is the DOCTYPE.
 fn is_doctype_html(file: &Path) -> Result<bool> {
 	if file.is_file() {
-		let file = File::open(file)?;
-		let reader = BufReader::new(file);
-		if let Some(Ok(first_line)) = reader.lines().next() {
+		let content = fs::read_to_string(file)?;
+		let (_, content) = split_front_matter(&content)?;
+		if let Some(first_line) = content.lines().next() {
 			if first_line.trim() == DOC_TYPE {
 				return Ok(true);
 			}
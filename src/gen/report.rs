@@ -0,0 +1,102 @@
+use crate::site::Site;
+use crate::Error;
+use pathdiff::diff_paths;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// Summary of a generation pass (what was rendered, copied, removed, or failed).
+#[derive(Debug, Default)]
+pub struct BuildReport {
+	/// html/markdown pages rendered to the dist dir.
+	pub rendered: usize,
+	/// other files copied as is.
+	pub copied: usize,
+	/// files skipped because their dist file is up to date (see build cache).
+	pub unchanged: usize,
+	/// draft and future pages not part of the output (build mode only).
+	pub unpublished: usize,
+	/// dist files removed (not coming from the content dir anymore).
+	pub removed: usize,
+	/// the stale dist files were not removed (non-empty dist dir without the marker file).
+	pub clean_skipped: bool,
+	/// files that could not be processed.
+	pub errors: Vec<FileError>,
+}
+
+/// A file that could not be processed, with the cause.
+#[derive(Debug)]
+pub struct FileError {
+	/// src file, relative to the root dir.
+	pub src_file: PathBuf,
+	pub cause: Error,
+}
+
+impl BuildReport {
+	pub fn add_error(&mut self, site: &Site, src_file: &Path, cause: Error) {
+		let src_file = diff_paths(src_file, site.root_dir()).unwrap_or_else(|| src_file.to_path_buf());
+		self.errors.push(FileError { src_file, cause });
+	}
+
+	/// Determine if the src file already has an error in this report.
+	pub fn has_error(&self, site: &Site, src_file: &Path) -> bool {
+		let src_file = diff_paths(src_file, site.root_dir()).unwrap_or_else(|| src_file.to_path_buf());
+		self.errors.iter().any(|error| error.src_file == src_file)
+	}
+
+	pub fn failed(&self) -> usize {
+		self.errors.len()
+	}
+
+	pub fn has_failures(&self) -> bool {
+		!self.errors.is_empty()
+	}
+}
+
+impl Display for BuildReport {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		if self.has_failures() {
+			// group the errors by kind, for a readable summary when many files fail for the same reason
+			let mut groups: BTreeMap<&str, Vec<&FileError>> = BTreeMap::new();
+			for error in self.errors.iter() {
+				groups.entry(error_kind(&error.cause)).or_default().push(error);
+			}
+
+			writeln!(f, "Build errors ({}):", self.errors.len())?;
+			for (kind, errors) in groups {
+				writeln!(f, "  {kind} ({}):", errors.len())?;
+				for error in errors {
+					writeln!(f, "    - {}\n        {}", error.src_file.display(), error.cause)?;
+				}
+			}
+		}
+
+		write!(
+			f,
+			"Build summary - pages rendered: {}, files copied: {}, unchanged: {}, unpublished: {}, files removed: {}, failed: {}",
+			self.rendered,
+			self.copied,
+			self.unchanged,
+			self.unpublished,
+			self.removed,
+			self.failed()
+		)
+	}
+}
+
+fn error_kind(error: &Error) -> &'static str {
+	match error {
+		Error::InvalidFrontMatter(_) => "Invalid front matter",
+		Error::TemplateError(_) => "Template error",
+		Error::DataFileError(_) => "Data file error",
+		Error::OutputCollision(..) => "Output collision",
+		Error::IOError(_) => "IO error",
+		Error::JsonError(_) => "JSON error",
+		Error::TomlError(_) => "TOML error",
+		_ => "Other error",
+	}
+}
+
+#[cfg(test)]
+#[path = "../_tests/tests_report.rs"]
+mod tests;
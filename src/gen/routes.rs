@@ -0,0 +1,114 @@
+//! Routes of the site, i.e., the source to output mapping of the content files (see `ssite routes`).
+
+use super::processor::{src_type_name, FileProcessor};
+use crate::prelude::*;
+use crate::site::Site;
+use pathdiff::diff_paths;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize)]
+pub struct Route {
+	/// src file, relative to the root dir.
+	pub src_file: PathBuf,
+	/// e.g., "PageMarkdown", "IndexHtml", "FrameHtml", "Other"
+	pub src_type: &'static str,
+	/// dist file, relative to the root dir (none for the frames).
+	pub dist_file: Option<PathBuf>,
+	/// public url, root relative (e.g., "/blog/hello").
+	pub url: Option<String>,
+	/// The frames of the page, from the closest to the farthest, relative to the root dir.
+	pub frames: Vec<PathBuf>,
+	/// False for the draft and future pages (not part of the build output).
+	pub published: bool,
+}
+
+/// The routes of the content files (sorted by src file).
+pub fn routes(site: &Site) -> Result<Vec<Route>> {
+	let rel = |path: &Path| diff_paths(path, site.root_dir()).unwrap_or_else(|| path.to_path_buf());
+
+	let mut routes = Vec::new();
+	for entry in site.content_entries() {
+		let src_file = entry.path();
+		let src_type = src_type_name(src_file);
+
+		let route = match FileProcessor::from_src_file(site, src_file.to_path_buf()) {
+			Some(processor) => {
+				let frames = if processor.is_for_html_render() {
+					processor.get_frames(site)?.iter().map(|f| rel(f)).collect()
+				} else {
+					Vec::new()
+				};
+				Route {
+					src_file: rel(src_file),
+					src_type,
+					dist_file: Some(rel(processor.dist_file())),
+					url: site.dist_url(processor.dist_file()),
+					frames,
+					// Note: The invalid front matter pages are reported as published (the build reports the error).
+					published: processor.is_published(site).unwrap_or(true),
+				}
+			}
+			// e.g., the frames
+			None => Route {
+				src_file: rel(src_file),
+				src_type,
+				dist_file: None,
+				url: None,
+				frames: Vec::new(),
+				published: true,
+			},
+		};
+		routes.push(route);
+	}
+
+	routes.sort_by(|a, b| a.src_file.cmp(&b.src_file));
+
+	Ok(routes)
+}
+
+/// The routes as a text table (one line per route, with the frame chain).
+pub fn routes_table(routes: &[Route]) -> String {
+	let header = ["SOURCE", "TYPE", "OUTPUT", "URL", "FRAMES"];
+	let rows: Vec<[String; 5]> = routes
+		.iter()
+		.map(|route| {
+			let dist_file = match (&route.dist_file, route.published) {
+				(Some(dist_file), true) => dist_file.display().to_string(),
+				(Some(_), false) => s!("(unpublished)"),
+				(None, _) => s!("-"),
+			};
+			let frames = route.frames.iter().map(|f| f.display().to_string()).collect::<Vec<_>>();
+			[
+				route.src_file.display().to_string(),
+				s!(route.src_type),
+				dist_file,
+				route.url.clone().unwrap_or_else(|| s!("-")),
+				if frames.is_empty() { s!("-") } else { frames.join(" > ") },
+			]
+		})
+		.collect();
+
+	let mut widths = header.map(|h| h.len());
+	for row in rows.iter() {
+		for (width, cell) in widths.iter_mut().zip(row.iter()) {
+			*width = (*width).max(cell.chars().count());
+		}
+	}
+
+	let line = |cells: [&str; 5]| {
+		let line: Vec<String> = cells.iter().zip(widths.iter()).map(|(cell, width)| f!("{cell:<width$}")).collect();
+		line.join("  ").trim_end().to_string()
+	};
+
+	let mut table = line(header);
+	for row in rows.iter() {
+		table.push('\n');
+		table.push_str(&line([&row[0], &row[1], &row[2], &row[3], &row[4]]));
+	}
+	table
+}
+
+#[cfg(test)]
+#[path = "../_tests/tests_routes.rs"]
+mod tests;
@@ -0,0 +1,136 @@
+//! Generated `sitemap.xml` (and optional `robots.txt`) of the rendered pages and the generated list pages,
+//! when the `[sitemap]` section is set.
+
+use super::collections::list_page_dist_files;
+use super::deps::DepGraph;
+use super::processor::FileProcessor;
+use crate::prelude::*;
+use crate::site::{SitemapConfig, Site};
+use crate::utils::xml_escape;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::fs;
+use std::path::{Path, PathBuf};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+const SITEMAP_FILE_NAME: &str = "sitemap.xml";
+const ROBOTS_FILE_NAME: &str = "robots.txt";
+
+/// A page of the sitemap.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SitemapUrl {
+	/// root relative url (e.g., "/blog/hello")
+	pub url: String,
+	/// RFC3339 last modification time (of the src file)
+	pub lastmod: Option<String>,
+}
+
+/// Write the sitemap (and robots) files of the rendered pages of the dependency graph, and of the list pages.
+/// Returns the written files (for the dist file set), none if no `[sitemap]` config.
+pub fn write_sitemap(site: &Site, dep_graph: &DepGraph) -> Result<Vec<PathBuf>> {
+	let config = match site.sitemap_config() {
+		Some(config) => config,
+		None => return Ok(Vec::new()),
+	};
+
+	let urls = sitemap_urls(site, dep_graph, config)?;
+
+	let mut files = Vec::new();
+
+	let sitemap_file = site.dist_dir().join(SITEMAP_FILE_NAME);
+	fs::write(&sitemap_file, sitemap_xml(&config.base_url, &urls))?;
+	files.push(sitemap_file);
+
+	if config.robots {
+		let robots_file = site.dist_dir().join(ROBOTS_FILE_NAME);
+		fs::write(&robots_file, robots_txt(&config.base_url))?;
+		files.push(robots_file);
+	}
+
+	Ok(files)
+}
+
+/// The dist files of the sitemap and robots (without writing them), none if no `[sitemap]` config.
+pub fn sitemap_dist_files(site: &Site) -> Vec<PathBuf> {
+	match site.sitemap_config() {
+		Some(config) if config.robots => vec![site.dist_dir().join(SITEMAP_FILE_NAME), site.dist_dir().join(ROBOTS_FILE_NAME)],
+		Some(_) => vec![site.dist_dir().join(SITEMAP_FILE_NAME)],
+		None => Vec::new(),
+	}
+}
+
+/// The sitemap urls (sorted), without the excluded ones.
+/// Note: The list pages (see `Collections`) have no src file, so, no lastmod.
+fn sitemap_urls(site: &Site, dep_graph: &DepGraph, config: &SitemapConfig) -> Result<Vec<SitemapUrl>> {
+	let exclude = build_exclude(&config.exclude)?;
+
+	let mut urls: Vec<SitemapUrl> = dep_graph
+		.pages()
+		.filter_map(|page| FileProcessor::from_src_file(site, page.to_path_buf()))
+		.filter_map(|processor| {
+			let url = site.dist_url(processor.dist_file())?;
+			let lastmod = file_modified(processor.src_file());
+			Some(SitemapUrl { url, lastmod })
+		})
+		.chain(list_page_dist_files(site).into_iter().filter_map(|dist_file| {
+			let url = site.dist_url(&dist_file)?;
+			Some(SitemapUrl { url, lastmod: None })
+		}))
+		.filter(|sitemap_url| !is_excluded(&exclude, &sitemap_url.url))
+		.collect();
+	urls.sort();
+
+	Ok(urls)
+}
+
+pub fn sitemap_xml(base_url: &str, urls: &[SitemapUrl]) -> String {
+	let mut xml = s!(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+	xml.push_str("\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+	for SitemapUrl { url, lastmod } in urls {
+		xml.push_str(&f!("  <url>\n    <loc>{}</loc>\n", xml_escape(&f!("{base_url}{url}"))));
+		if let Some(lastmod) = lastmod {
+			xml.push_str(&f!("    <lastmod>{lastmod}</lastmod>\n"));
+		}
+		xml.push_str("  </url>\n");
+	}
+	xml.push_str("</urlset>\n");
+	xml
+}
+
+pub fn robots_txt(base_url: &str) -> String {
+	f!("User-agent: *\nAllow: /\n\nSitemap: {base_url}/{SITEMAP_FILE_NAME}\n")
+}
+
+// region:    --- Utils
+
+fn build_exclude(patterns: &[String]) -> Result<Gitignore> {
+	let mut builder = GitignoreBuilder::new("/");
+	for pattern in patterns {
+		builder
+			.add_line(None, pattern)
+			.map_err(|ex| Error::InvalidConfigValue(f!("sitemap.exclude '{pattern}'"), s!(ex)))?;
+	}
+	builder.build().map_err(|ex| Error::InvalidConfigValue(s!("sitemap.exclude"), s!(ex)))
+}
+
+/// Match the url path (e.g., "/blog/hello" or "/blog/") as a path relative to the site root.
+fn is_excluded(exclude: &Gitignore, url: &str) -> bool {
+	let is_dir = url.ends_with('/');
+	let path = url.trim_matches('/');
+	if path.is_empty() {
+		return false;
+	}
+	exclude.matched_path_or_any_parents(Path::new(path), is_dir).is_ignore()
+}
+
+fn file_modified(file: &Path) -> Option<String> {
+	let modified = fs::metadata(file).ok()?.modified().ok()?;
+	let modified = OffsetDateTime::from(modified).replace_nanosecond(0).ok()?;
+	modified.format(&Rfc3339).ok()
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_sitemap.rs"]
+mod tests;
@@ -0,0 +1,103 @@
+//! Named content slots, from the page to its frames.
+//!
+//! - A page defines a slot with a `SLOT(name)` line, the content, and an `END_SLOT` line.
+//!   The slot content is included as is (not markdown rendered) and removed from the page body.
+//! - A frame places a slot with `INCLUDE_SLOT(name)`, or `INCLUDE_SLOT(name)default content END_SLOT`
+//!   for a default content when the page does not define the slot.
+
+use crate::consts::{END_SLOT, INCLUDE_SLOT, SLOT};
+use crate::prelude::*;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+// Note: Compiled once, as used for each page and frame render.
+static SLOT_RX: LazyLock<Regex> = LazyLock::new(|| {
+	Regex::new(&f!(
+		r"(?ms)^[ \t]*{SLOT}\(([\w-]+)\)[ \t]*\r?\n(.*?)^[ \t]*{END_SLOT}[ \t]*(\r?\n|\z)"
+	))
+	.unwrap()
+});
+static INCLUDE_SLOT_RX: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(&f!(r"{INCLUDE_SLOT}\(([\w-]+)\)|{END_SLOT}")).unwrap());
+static MARKDOWN_SLOT_RX: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(&f!(r"<p>({INCLUDE_SLOT}\([\w-]+\)(?:[^<]*?{END_SLOT})?)</p>")).unwrap());
+
+#[derive(Debug, Default)]
+pub struct Slots {
+	slots: HashMap<String, String>,
+}
+
+impl Slots {
+	pub fn get(&self, name: &str) -> Option<&str> {
+		self.slots.get(name).map(|s| s.as_str())
+	}
+}
+
+/// Extract the `SLOT(name) ... END_SLOT` blocks from the page content.
+/// Returns the slots and the content without the slot blocks.
+pub fn extract_slots(content: &str) -> (Slots, String) {
+	let mut slots = Slots::default();
+	let content = SLOT_RX.replace_all(content, |caps: &Captures| {
+		slots.slots.insert(s!(&caps[1]), s!(caps[2].trim()));
+		""
+	});
+
+	(slots, content.to_string())
+}
+
+/// Replace the `INCLUDE_SLOT(name)` markers (with their eventual default content) of the frame content.
+/// Slots not defined by the page, and without default, are replaced by an empty string.
+pub fn include_slots(frame_content: &str, slots: &Slots) -> String {
+	let markers: Vec<Captures> = INCLUDE_SLOT_RX.captures_iter(frame_content).collect();
+
+	let mut res = String::with_capacity(frame_content.len());
+	let mut last = 0;
+	let mut i = 0;
+
+	while i < markers.len() {
+		let marker = &markers[i];
+		let whole = marker.get(0).unwrap();
+
+		// Note: A stray END_SLOT is left as is.
+		let name = match marker.get(1) {
+			Some(name) => name.as_str(),
+			None => {
+				i += 1;
+				continue;
+			}
+		};
+
+		res.push_str(&frame_content[last..whole.start()]);
+
+		// If the next marker is an END_SLOT, then, what is in between is the default content.
+		let default_end = markers.get(i + 1).and_then(|next| next.get(1).is_none().then(|| next.get(0).unwrap()));
+		let default = default_end.map(|end| frame_content[whole.end()..end.start()].trim());
+
+		res.push_str(slots.get(name).or(default).unwrap_or_default());
+
+		match default_end {
+			Some(end) => {
+				last = end.end();
+				i += 2;
+			}
+			None => {
+				last = whole.end();
+				i += 1;
+			}
+		}
+	}
+	res.push_str(&frame_content[last..]);
+
+	res
+}
+
+/// Remove the `<p></p>` markdown wraps around the slot markers (e.g., `<p>INCLUDE_SLOT(head)</p>`).
+/// Note: For markdown frames, only text defaults (no html tags) are unwrapped.
+pub fn unwrap_markdown_slots(html: &str) -> String {
+	MARKDOWN_SLOT_RX.replace_all(html, "$1").to_string()
+}
+
+#[cfg(test)]
+#[path = "../_tests/tests_slots.rs"]
+mod tests;
@@ -1,22 +1,25 @@
-#![allow(unused)] // silence unused warnings while exploring (to comment out)
 use crate::prelude::*;
 use cmd::cmd_run;
 
+mod check;
 mod cmd;
 mod consts;
 mod error;
 mod gen;
 mod prelude;
+mod publish;
+mod scaffold;
+mod server;
 mod site;
 mod utils;
 
 #[tokio::main]
 async fn main() {
-	let d = s!("some");
 	match cmd_run().await {
 		Ok(_) => println!("✔ All good and well"),
 		Err(e) => {
-			println!("Error:\n  {}", e)
+			println!("Error:\n  {}", e);
+			std::process::exit(1);
 		}
 	};
 }
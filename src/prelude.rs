@@ -5,9 +5,6 @@ pub use std::format as f;
 // --- Application defualt Result
 pub type Result<T> = core::result::Result<T, Error>;
 
-// --- Generic Wrapper newtype pattern, mostly for external type to type From/TryFrom conversions
-pub struct W<T>(pub T);
-
 // --- Personal preference
 macro_rules! s {
 	() => {
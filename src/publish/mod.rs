@@ -0,0 +1,157 @@
+//! Publish (sync) the site dist dir to a S3 (or S3-compatible) bucket.
+//!
+//! Files are compared by content hash (local md5 vs. remote ETag), so only the changed files are uploaded,
+//! and the bucket keys that do not have a dist file anymore are deleted.
+//! Without a `bucket_root`, the stale keys are the ones of the whole bucket, so, they are only deleted when confirmed
+//! (`--delete-all`).
+
+use crate::consts::DIST_MARKER_FILE_NAME;
+use crate::prelude::*;
+use crate::site::Site;
+use crate::utils::md5_hex;
+use pathdiff::diff_paths;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+
+use self::s3::S3Bucket;
+
+mod s3;
+
+#[derive(Debug)]
+struct LocalFile {
+	key: String,
+	path: PathBuf,
+	md5: String,
+}
+
+/// What needs to be done to have the bucket match the dist dir.
+#[derive(Debug, Default)]
+struct PublishPlan {
+	uploads: Vec<LocalFile>,
+	deletes: Vec<String>,
+	unchanged: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct PublishReport {
+	pub uploaded: usize,
+	pub deleted: usize,
+	pub unchanged: usize,
+	/// stale keys not deleted (no `bucket_root`, and not confirmed).
+	pub kept: usize,
+	pub dry_run: bool,
+}
+
+impl Display for PublishReport {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let prefix = if self.dry_run { "Publish summary (dry run)" } else { "Publish summary" };
+		write!(
+			f,
+			"{prefix} - uploaded: {}, deleted: {}, unchanged: {}",
+			self.uploaded, self.deleted, self.unchanged
+		)?;
+		if self.kept > 0 {
+			write!(
+				f,
+				"\nWARNING - {} stale key(s) not deleted, as no [publish] bucket_root is set (the whole bucket). Set the bucket_root, or use --delete-all to delete them.",
+				self.kept
+			)?;
+		}
+		Ok(())
+	}
+}
+
+/// Sync the site dist dir to the bucket of the `[publish]` config.
+/// When `dry_run`, only print what would be uploaded and deleted.
+/// Without a `bucket_root`, the stale keys are only deleted when `delete_all` (otherwise, they are listed as kept).
+pub async fn publish(site: &Site, dry_run: bool, delete_all: bool) -> Result<PublishReport> {
+	let config = site.publish_config().ok_or(Error::InvalidS3Config)?;
+	let bucket_root = config.bucket_root.as_deref();
+
+	let local_files = local_files(site, bucket_root)?;
+
+	let bucket = S3Bucket::from_config(config).await?;
+	let remote_etags = bucket.list_etags(bucket_root).await?;
+
+	let plan = build_plan(local_files, &remote_etags);
+
+	for file in plan.uploads.iter() {
+		let rel_path = diff_paths(&file.path, site.root_dir()).unwrap_or_else(|| file.path.to_path_buf());
+		println!("- upload:     {:<40} >>    s3://{}/{}", rel_path.display(), config.bucket_name, file.key);
+		if !dry_run {
+			bucket.put_file(&file.key, &file.path, &site.dist_content_type(&file.path)).await?;
+		}
+	}
+
+	// guard - without bucket_root, the stale keys might not come from the site (e.g., other sites of the bucket)
+	let can_delete = bucket_root.is_some() || delete_all;
+	for key in plan.deletes.iter() {
+		if can_delete {
+			println!("- delete:     s3://{}/{key}", config.bucket_name);
+		} else {
+			println!("- keep (no bucket_root): s3://{}/{key}", config.bucket_name);
+		}
+	}
+	if !dry_run && can_delete {
+		bucket.delete_keys(&plan.deletes).await?;
+	}
+
+	let (deleted, kept) = if can_delete { (plan.deletes.len(), 0) } else { (0, plan.deletes.len()) };
+
+	Ok(PublishReport {
+		uploaded: plan.uploads.len(),
+		deleted,
+		unchanged: plan.unchanged,
+		kept,
+		dry_run,
+	})
+}
+
+/// Build the upload/delete plan from the local files and the remote key/etag map.
+fn build_plan(local_files: Vec<LocalFile>, remote_etags: &HashMap<String, String>) -> PublishPlan {
+	let mut plan = PublishPlan::default();
+	let local_keys: HashSet<String> = local_files.iter().map(|f| f.key.to_string()).collect();
+
+	for file in local_files {
+		match remote_etags.get(&file.key) {
+			Some(etag) if etag.trim_matches('"') == file.md5 => plan.unchanged += 1,
+			_ => plan.uploads.push(file),
+		}
+	}
+
+	let mut deletes: Vec<String> = remote_etags.keys().filter(|key| !local_keys.contains(*key)).cloned().collect();
+	deletes.sort();
+	plan.deletes = deletes;
+
+	plan
+}
+
+/// The dist files with their bucket key and md5.
+fn local_files(site: &Site, bucket_root: Option<&str>) -> Result<Vec<LocalFile>> {
+	let mut files: Vec<LocalFile> = Vec::new();
+
+	// Note: The dist dir marker file is not part of the site.
+	for entry in site.dist_entries().filter(|e| e.file_name() != DIST_MARKER_FILE_NAME) {
+		let path = entry.path().to_path_buf();
+		let rel_path = match path.strip_prefix(site.dist_dir()) {
+			Ok(rel_path) => rel_path.to_string_lossy().replace('\\', "/"),
+			Err(_) => continue,
+		};
+		let key = match bucket_root {
+			Some(root) => f!("{root}/{rel_path}"),
+			None => rel_path,
+		};
+		let md5 = md5_hex(&fs::read(&path)?);
+		files.push(LocalFile { key, path, md5 });
+	}
+
+	files.sort_by(|a, b| a.key.cmp(&b.key));
+
+	Ok(files)
+}
+
+#[cfg(test)]
+#[path = "../_tests/tests_publish.rs"]
+mod tests;
@@ -0,0 +1,122 @@
+use crate::prelude::*;
+use crate::site::{BucketCred, PublishConfig};
+use aws_config::meta::region::RegionProviderChain;
+use aws_config::BehaviorVersion;
+use aws_sdk_s3::config::Region;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{Delete, ObjectIdentifier};
+use aws_sdk_s3::Client;
+use std::collections::HashMap;
+use std::path::Path;
+
+// Note: Region used when none is configured (e.g., local S3-compatible server).
+const FALLBACK_REGION: &str = "us-east-1";
+// Note: Max number of keys per DeleteObjects request.
+const DELETE_BATCH_SIZE: usize = 1000;
+
+pub struct S3Bucket {
+	client: Client,
+	name: String,
+}
+
+/// Makers
+impl S3Bucket {
+	pub async fn from_config(config: &PublishConfig) -> Result<Self> {
+		let region = RegionProviderChain::first_try(config.bucket_region.clone().map(Region::new))
+			.or_default_provider()
+			.or_else(FALLBACK_REGION);
+
+		let mut loader = aws_config::defaults(BehaviorVersion::latest()).region(region);
+		if let BucketCred::Profile(profile) = &config.bucket_cred {
+			loader = loader.profile_name(profile);
+		}
+		let sdk_config = loader.load().await;
+
+		let mut builder = aws_sdk_s3::config::Builder::from(&sdk_config);
+		if let Some(endpoint) = &config.bucket_endpoint {
+			// Note: S3-compatible servers usually do not support the virtual hosted style.
+			builder = builder.endpoint_url(endpoint).force_path_style(true);
+		}
+
+		Ok(S3Bucket {
+			client: Client::from_conf(builder.build()),
+			name: config.bucket_name.to_string(),
+		})
+	}
+}
+
+impl S3Bucket {
+	/// Return the key/etag of all the objects under the eventual root.
+	pub async fn list_etags(&self, root: Option<&str>) -> Result<HashMap<String, String>> {
+		let mut etags: HashMap<String, String> = HashMap::new();
+		let prefix = root.map(|root| f!("{root}/"));
+		let mut continuation_token: Option<String> = None;
+
+		loop {
+			let res = self
+				.client
+				.list_objects_v2()
+				.bucket(&self.name)
+				.set_prefix(prefix.clone())
+				.set_continuation_token(continuation_token.take())
+				.send()
+				.await
+				.map_err(|ex| Error::S3Error(s!(ex.into_service_error())))?;
+
+			for object in res.contents() {
+				if let (Some(key), Some(etag)) = (object.key(), object.e_tag()) {
+					etags.insert(s!(key), s!(etag));
+				}
+			}
+
+			match res.next_continuation_token() {
+				Some(token) if res.is_truncated().unwrap_or(false) => continuation_token = Some(s!(token)),
+				_ => break,
+			}
+		}
+
+		Ok(etags)
+	}
+
+	pub async fn put_file(&self, key: &str, file: &Path, content_type: &str) -> Result<()> {
+		let body = ByteStream::from_path(file).await.map_err(|ex| Error::S3Error(s!(ex)))?;
+
+		self
+			.client
+			.put_object()
+			.bucket(&self.name)
+			.key(key)
+			.content_type(content_type)
+			.body(body)
+			.send()
+			.await
+			.map_err(|ex| Error::S3Error(s!(ex.into_service_error())))?;
+
+		Ok(())
+	}
+
+	pub async fn delete_keys(&self, keys: &[String]) -> Result<()> {
+		for keys in keys.chunks(DELETE_BATCH_SIZE) {
+			let objects = keys
+				.iter()
+				.map(|key| ObjectIdentifier::builder().key(key).build())
+				.collect::<core::result::Result<Vec<_>, _>>()
+				.map_err(|ex| Error::S3Error(s!(ex)))?;
+			let delete = Delete::builder()
+				.set_objects(Some(objects))
+				.build()
+				.map_err(|ex| Error::S3Error(s!(ex)))?;
+
+			self
+				.client
+				.delete_objects()
+				.bucket(&self.name)
+				.delete(delete)
+				.send()
+				.await
+				.map_err(|ex| Error::S3Error(s!(ex.into_service_error())))?;
+		}
+
+		Ok(())
+	}
+}
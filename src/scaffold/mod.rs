@@ -0,0 +1,170 @@
+//! Scaffolding of a new site (`ssite init`) and of new content files from archetypes (`ssite new`).
+//!
+//! The archetypes are the files of the archetypes dir (e.g., `archetypes/page.md`), with the `{{title}}`, `{{date}}`,
+//! and `{{slug}}` placeholders filled in (the other handlebars expressions are kept for the page rendering).
+
+use crate::consts::CONFIG_FILE_NAME;
+use crate::prelude::*;
+use crate::site::Site;
+use std::fs;
+use std::path::{Path, PathBuf};
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+const DEFAULT_ARCHETYPE: &str = "page";
+
+const INIT_FRAME: &str = include_str!("init/_frame.html");
+const INIT_INDEX: &str = include_str!("init/index.html");
+const INIT_HELLO: &str = include_str!("init/hello.md");
+const INIT_ARCHETYPE_PAGE: &str = include_str!("init/page.md");
+
+// region:    --- Init
+
+/// Scaffold a new site in the dir (config, content with frame, index and sample page, and the page archetype).
+/// The existing files are not overwritten. Returns the created files.
+pub fn init_site(dir: &Path, with_runners: bool) -> Result<Vec<PathBuf>> {
+	if dir.join(CONFIG_FILE_NAME).exists() {
+		return Err(Error::FileAlreadyExists(dir.join(CONFIG_FILE_NAME).display().to_string()));
+	}
+
+	let title = dir
+		.canonicalize()?
+		.file_name()
+		.map(|name| title_from_slug(&name.to_string_lossy()))
+		.unwrap_or_else(|| s!("My Site"));
+
+	let files = [
+		(s!(CONFIG_FILE_NAME), config_toml(&title, with_runners)),
+		(s!("content/_frame.html"), s!(INIT_FRAME)),
+		(s!("content/index.html"), s!(INIT_INDEX)),
+		(s!("content/hello.md"), s!(INIT_HELLO)),
+		(f!("archetypes/{DEFAULT_ARCHETYPE}.md"), s!(INIT_ARCHETYPE_PAGE)),
+		(s!(".gitignore"), s!("_site/\n.ssite/\n")),
+	];
+
+	let mut created = Vec::new();
+	for (rel_path, content) in files {
+		let file = dir.join(rel_path);
+		if file.exists() {
+			println!("- skip (exists): {}", file.display());
+			continue;
+		}
+		write_new_file(&file, &content)?;
+		println!("- create:     {}", file.display());
+		created.push(file);
+	}
+
+	Ok(created)
+}
+
+fn config_toml(title: &str, with_runners: bool) -> String {
+	let mut toml = f!(r#"[source]
+content_dir = "content/"
+dist_dir = "_site"
+# partials_dir = "_partials"      # relative to the content_dir
+# data_dir = "_data"              # relative to the content_dir
+# archetypes_dir = "archetypes"   # relative to the root dir (for `ssite new`)
+# url_style = "extensionless"     # "extensionless" | "directory" | "html"
+# on_collision = "error"          # "error" | "html" | "markdown" (same output file, e.g., hello.md and hello.html)
+# template = true                 # render the pages and frames with handlebars (front matter `template` overrides)
+# keep = ["CNAME"]                # dist files never removed by the clean
+
+[site]
+title = "{title}"
+
+[dev]
+port = 8080
+"#);
+
+	if with_runners {
+		toml.push_str(
+			r#"
+# Runners are run before the build (args), and kept running in dev (watch_args).
+[runner.css]
+cmd = "echo"
+args = ["TODO: css build command"]
+watch_args = ["TODO: css watch command"]
+
+[runner.js]
+cmd = "echo"
+args = ["TODO: js build command"]
+watch_args = ["TODO: js watch command"]
+"#,
+		);
+	}
+
+	toml
+}
+
+// endregion: --- Init
+
+// region:    --- New
+
+/// Create a new content file from the archetype (e.g., "page" is `archetypes/page.md`).
+/// The `rel_path` is relative to the content dir (e.g., `blog/my-post`, `.md` by default).
+/// Returns the created file.
+pub fn new_content(site: &Site, archetype: &str, rel_path: &str, title: Option<&str>) -> Result<PathBuf> {
+	let mut file = site.content_dir().join(rel_path.trim_start_matches('/'));
+	if file.extension().is_none() {
+		file.set_extension("md");
+	}
+	if file.exists() {
+		return Err(Error::FileAlreadyExists(file.display().to_string()));
+	}
+
+	let ext = file.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+	let archetype_file = site.archetypes_dir().join(f!("{archetype}.{ext}"));
+	let archetype_content = match fs::read_to_string(&archetype_file) {
+		Ok(content) => content,
+		// Note: The default archetype is built in (the init one), so that it works without the archetypes dir.
+		Err(_) if archetype == DEFAULT_ARCHETYPE && ext == "md" => s!(INIT_ARCHETYPE_PAGE),
+		Err(_) => return Err(Error::MissingArchetype(archetype_file.display().to_string())),
+	};
+
+	let slug = file.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+	let title = title.map(|t| t.to_string()).unwrap_or_else(|| title_from_slug(&slug));
+	let date = OffsetDateTime::now_utc()
+		.format(format_description!("[year]-[month]-[day]"))
+		.unwrap_or_default();
+
+	write_new_file(&file, &fill_archetype(&archetype_content, &title, &date, &slug))?;
+
+	Ok(file)
+}
+
+/// Replace the `{{title}}`, `{{date}}`, and `{{slug}}` placeholders of the archetype.
+/// The title is escaped for a double quoted front matter value (same escapes in YAML and TOML).
+pub fn fill_archetype(content: &str, title: &str, date: &str, slug: &str) -> String {
+	content
+		.replace("{{title}}", &title.replace('\\', "\\\\").replace('"', "\\\""))
+		.replace("{{date}}", date)
+		.replace("{{slug}}", slug)
+}
+
+// endregion: --- New
+
+// region:    --- Utils
+
+/// The title of a slug or file name (e.g., "my-first-post" is "My first post").
+pub fn title_from_slug(slug: &str) -> String {
+	let words = slug.replace(['-', '_'], " ");
+	let mut chars = words.trim().chars();
+	match chars.next() {
+		Some(first) => first.to_uppercase().chain(chars).collect(),
+		None => s!(),
+	}
+}
+
+fn write_new_file(file: &Path, content: &str) -> Result<()> {
+	if let Some(dir) = file.parent() {
+		fs::create_dir_all(dir)?;
+	}
+	fs::write(file, content)?;
+	Ok(())
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_scaffold.rs"]
+mod tests;
@@ -0,0 +1,27 @@
+// ssite dev - live reload client (injected in the html pages by the dev server)
+(function () {
+	var source = new EventSource("/__ssite/livereload");
+
+	source.onmessage = function (evt) {
+		var paths = evt.data.split("\n");
+		var cssOnly = paths.every(function (path) {
+			return /\.css$/.test(path);
+		});
+
+		if (!cssOnly) {
+			location.reload();
+			return;
+		}
+
+		// Only css changed, so, we hot swap the stylesheets (all of them if none match the changed paths).
+		var links = Array.prototype.slice.call(document.querySelectorAll('link[rel="stylesheet"]'));
+		var matches = links.filter(function (link) {
+			return paths.indexOf(new URL(link.href).pathname) > -1;
+		});
+		(matches.length > 0 ? matches : links).forEach(function (link) {
+			var url = new URL(link.href);
+			url.searchParams.set("ssite-reload", Date.now());
+			link.href = url.toString();
+		});
+	};
+})();
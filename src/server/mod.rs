@@ -0,0 +1,282 @@
+//! Minimal local http server for `ssite dev`, serving the site `dist_dir`.
+//!
+//! Only `GET` and `HEAD` are supported, and each connection is closed after the response,
+//! except for the live reload event stream (Server-Sent Events) which stays open.
+
+use crate::consts::HTML_CONTENT_TYPE;
+use crate::prelude::*;
+use crate::site::{Site, UrlStyle};
+use crate::utils::{content_type, is_extensionless_page, percent_decode};
+use std::path::{Component, Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+const LIVE_RELOAD_EVENTS_PATH: &str = "/__ssite/livereload";
+const LIVE_RELOAD_SCRIPT_PATH: &str = "/__ssite/livereload.js";
+const LIVE_RELOAD_SCRIPT: &str = include_str!("livereload.js");
+
+pub struct DevServer {
+	listener: TcpListener,
+	dist_dir: PathBuf,
+	/// For the content type of the extension-less files (see `is_extensionless_page`).
+	content_dir: PathBuf,
+	url_style: UrlStyle,
+	live_reload: LiveReload,
+}
+
+/// The resolution of a request target to the dist dir.
+#[derive(Debug, PartialEq, Eq)]
+enum DistTarget {
+	File(PathBuf),
+	/// A directory requested without its trailing slash, redirected to it (e.g., `/blog` to `/blog/`),
+	/// so that the relative links of its `index.html` resolve as on the static hosts.
+	Redirect(String),
+}
+
+// region:    --- LiveReload
+
+/// Notifier of the browsers connected to the dev server that some dist files changed.
+#[derive(Clone)]
+pub struct LiveReload {
+	tx: broadcast::Sender<String>,
+}
+
+impl LiveReload {
+	fn new() -> Self {
+		let (tx, _) = broadcast::channel(16);
+		LiveReload { tx }
+	}
+
+	/// Notify the connected browsers that the given dist files changed.
+	/// Note: CSS only changes get hot swapped, anything else triggers a full page reload.
+	pub fn notify(&self, dist_dir: &Path, dist_files: &[PathBuf]) {
+		let paths: Vec<String> = dist_files
+			.iter()
+			.filter_map(|file| file.strip_prefix(dist_dir).ok())
+			.map(|rel| f!("/{}", rel.to_string_lossy().replace('\\', "/")))
+			.collect();
+
+		if !paths.is_empty() {
+			// Note: Only fails when no browser is listening, which is fine.
+			let _ = self.tx.send(paths.join("\n"));
+		}
+	}
+}
+
+// endregion: --- LiveReload
+
+/// Makers
+impl DevServer {
+	/// Bind the server on the `[dev] host/port` of the site config.
+	pub async fn bind(site: &Site) -> Result<Self> {
+		let dev_config = site.dev_config();
+		let listener = TcpListener::bind((dev_config.host.as_str(), dev_config.port)).await?;
+		Ok(DevServer {
+			listener,
+			dist_dir: site.dist_dir().to_path_buf(),
+			content_dir: site.content_dir().to_path_buf(),
+			url_style: site.url_style(),
+			live_reload: LiveReload::new(),
+		})
+	}
+}
+
+impl DevServer {
+	pub fn local_url(&self) -> Result<String> {
+		let addr = self.listener.local_addr()?;
+		Ok(f!("http://{addr}/"))
+	}
+
+	pub fn live_reload(&self) -> LiveReload {
+		self.live_reload.clone()
+	}
+
+	/// Accept connections until the process ends.
+	pub async fn serve(self) {
+		loop {
+			match self.listener.accept().await {
+				Ok((stream, _)) => {
+					let (dist_dir, content_dir) = (self.dist_dir.clone(), self.content_dir.clone());
+					let url_style = self.url_style;
+					let live_reload = self.live_reload.clone();
+					tokio::spawn(async move {
+						if let Err(ex) = handle_connection(stream, &dist_dir, &content_dir, url_style, &live_reload).await {
+							println!("dev server - connection error: {ex}");
+						}
+					});
+				}
+				Err(ex) => println!("dev server - accept error: {ex}"),
+			}
+		}
+	}
+}
+
+async fn handle_connection(
+	mut stream: TcpStream,
+	dist_dir: &Path,
+	content_dir: &Path,
+	url_style: UrlStyle,
+	live_reload: &LiveReload,
+) -> Result<()> {
+	let head = match read_request_head(&mut stream).await? {
+		Some(head) => head,
+		None => return Ok(()),
+	};
+
+	// e.g., "GET /some/path?query HTTP/1.1"
+	let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+	let (method, target) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+
+	if method != "GET" && method != "HEAD" {
+		return write_response(&mut stream, "405 Method Not Allowed", "text/plain", b"Method Not Allowed", true).await;
+	}
+
+	let with_body = method == "GET";
+
+	// --- The live reload endpoints
+	let path = target.split(['?', '#']).next().unwrap_or_default();
+	if path == LIVE_RELOAD_EVENTS_PATH {
+		return stream_live_reload_events(&mut stream, live_reload).await;
+	}
+	if path == LIVE_RELOAD_SCRIPT_PATH {
+		let body = LIVE_RELOAD_SCRIPT.as_bytes();
+		return write_response(&mut stream, "200 OK", "text/javascript", body, with_body).await;
+	}
+
+	// --- The dist files
+	match resolve_dist_file(dist_dir, target) {
+		Some(DistTarget::Redirect(location)) => write_redirect(&mut stream, &location).await,
+		Some(DistTarget::File(file)) => {
+			let mut content = tokio::fs::read(&file).await?;
+			let page = is_extensionless_page(&file, dist_dir, content_dir, url_style);
+			let content_type = content_type(&file, page);
+			if content_type == HTML_CONTENT_TYPE {
+				content = inject_live_reload_script(content);
+			}
+			write_response(&mut stream, "200 OK", &content_type, &content, with_body).await
+		}
+		None => write_response(&mut stream, "404 Not Found", "text/plain", b"Not Found", true).await,
+	}
+}
+
+/// Read the request up to the end of the headers (we do not support request bodies).
+async fn read_request_head(stream: &mut TcpStream) -> Result<Option<String>> {
+	let mut data: Vec<u8> = Vec::new();
+	let mut buf = [0u8; 1024];
+
+	loop {
+		let n = stream.read(&mut buf).await?;
+		if n == 0 {
+			return Ok(None);
+		}
+		data.extend_from_slice(&buf[..n]);
+		if data.windows(4).any(|w| w == b"\r\n\r\n") || data.len() > MAX_REQUEST_HEAD {
+			break;
+		}
+	}
+
+	Ok(Some(String::from_utf8_lossy(&data).to_string()))
+}
+
+async fn write_response(
+	stream: &mut TcpStream,
+	status: &str,
+	content_type: &str,
+	body: &[u8],
+	with_body: bool,
+) -> Result<()> {
+	let head = f!(
+		"HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+		body.len()
+	);
+	stream.write_all(head.as_bytes()).await?;
+	if with_body {
+		stream.write_all(body).await?;
+	}
+	stream.flush().await?;
+	Ok(())
+}
+
+async fn write_redirect(stream: &mut TcpStream, location: &str) -> Result<()> {
+	let head = f!(
+		"HTTP/1.1 301 Moved Permanently\r\nLocation: {location}\r\nContent-Length: 0\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n"
+	);
+	stream.write_all(head.as_bytes()).await?;
+	stream.flush().await?;
+	Ok(())
+}
+
+/// Keep the connection open and send a Server-Sent Event for each live reload notification.
+async fn stream_live_reload_events(stream: &mut TcpStream, live_reload: &LiveReload) -> Result<()> {
+	let mut rx = live_reload.tx.subscribe();
+
+	let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n";
+	stream.write_all(head.as_bytes()).await?;
+	stream.flush().await?;
+
+	loop {
+		match rx.recv().await {
+			Ok(paths) => {
+				// Note: Multi-line data is sent as one `data:` line per path (the browser joins them with `\n`).
+				let data: String = paths.lines().map(|path| f!("data: {path}\n")).collect();
+				stream.write_all(f!("{data}\n").as_bytes()).await?;
+				stream.flush().await?;
+			}
+			Err(broadcast::error::RecvError::Lagged(_)) => continue,
+			Err(broadcast::error::RecvError::Closed) => return Ok(()),
+		}
+	}
+}
+
+// region:    --- Utils
+
+/// Add the live reload client script just before the last `</body>` (or at the end if none).
+fn inject_live_reload_script(content: Vec<u8>) -> Vec<u8> {
+	let script = f!("<script src=\"{LIVE_RELOAD_SCRIPT_PATH}\"></script>");
+	let mut html = String::from_utf8_lossy(&content).to_string();
+	match html.to_ascii_lowercase().rfind("</body>") {
+		Some(idx) => html.insert_str(idx, &script),
+		None => html.push_str(&script),
+	}
+	html.into_bytes()
+}
+
+/// Resolve a request target (e.g., `/sub-dir/page?q=1`) to a file of the dist dir.
+/// - Directories resolve to their `index.html`, or, without the trailing slash, to a redirect to it.
+/// - Targets escaping the dist dir (e.g., `..`, or its `%2E%2E` escape) resolve to None.
+fn resolve_dist_file(dist_dir: &Path, target: &str) -> Option<DistTarget> {
+	let raw_path = target.split(['?', '#']).next().unwrap_or_default();
+	let path = percent_decode(raw_path);
+
+	let mut file = dist_dir.to_path_buf();
+	for component in Path::new(path.trim_start_matches('/')).components() {
+		match component {
+			Component::Normal(name) => file.push(name),
+			Component::CurDir => (),
+			_ => return None,
+		}
+	}
+
+	if file.is_dir() {
+		if !raw_path.ends_with('/') {
+			let query = &target[raw_path.len()..];
+			return Some(DistTarget::Redirect(f!("{raw_path}/{query}")));
+		}
+		file.push("index.html");
+	}
+
+	if file.is_file() {
+		Some(DistTarget::File(file))
+	} else {
+		None
+	}
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_server.rs"]
+mod tests;
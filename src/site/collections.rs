@@ -0,0 +1,49 @@
+//! The collections of a build, i.e., the pages of the `[[collection]]` content dirs.
+//! Loaded by the generation (see `gen::collections`), and used by the rendering of the lists.
+
+use super::CollectionConfig;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Collections {
+	pub(crate) collections: Vec<Collection>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Collection {
+	pub(crate) config: CollectionConfig,
+	/// The url of the collection dir index page (e.g., `blog/index.md`), if any.
+	pub(crate) index_url: Option<String>,
+	pub(crate) items: Vec<CollectionItem>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionItem {
+	pub title: String,
+	/// root relative url (e.g., "/blog/hello")
+	pub url: String,
+	pub date: Option<String>,
+	pub tags: Vec<String>,
+}
+
+impl Collections {
+	pub fn get(&self, name: &str) -> Option<&Collection> {
+		self.collections.iter().find(|c| c.config.name == name)
+	}
+}
+
+impl Collection {
+	pub fn total_pages(&self) -> usize {
+		match self.config.per_page {
+			Some(per_page) => self.items.len().div_ceil(per_page).max(1),
+			None => 1,
+		}
+	}
+
+	/// The items of the 1 based page.
+	pub fn page_items(&self, page: usize) -> Vec<CollectionItem> {
+		match self.config.per_page {
+			Some(per_page) => self.items.iter().skip((page - 1) * per_page).take(per_page).cloned().collect(),
+			None => self.items.clone(),
+		}
+	}
+}
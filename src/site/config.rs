@@ -1,6 +1,8 @@
+use crate::consts::CONFIG_FILE_NAME;
 use crate::utils::assert_valid_dir;
-use crate::utils::toml::{toml_as_string, DeepGet};
+use crate::utils::toml::{toml_to_json, DeepGet};
 use crate::{f, s, Error};
+use serde_json::{Map, Value as JsonValue};
 use std::collections::HashSet;
 use std::fs::{create_dir_all, read_to_string};
 use std::path::{Path, PathBuf};
@@ -8,16 +10,43 @@ use std::str::FromStr;
 use strum_macros::EnumString;
 use toml::Value;
 
-use super::{RunMode, Runner};
+use super::RunMode;
 
-const CONFIG_FILE_NAME: &str = "ssite.toml";
+const DEFAULT_PARTIALS_DIR: &str = "_partials";
+const DEFAULT_DATA_DIR: &str = "_data";
+const DEFAULT_ARCHETYPES_DIR: &str = "archetypes";
+const DEFAULT_FEED_LIMIT: usize = 20;
+const DEFAULT_DEV_HOST: &str = "127.0.0.1";
+const DEFAULT_DEV_PORT: u16 = 8080;
 
 #[derive(Debug)]
 pub struct SiteConfig {
 	pub root_dir: PathBuf,
 	pub content_dir: PathBuf,
 	pub dist_dir: PathBuf,
+	pub partials_dir: PathBuf,
+	/// The global data files dir (e.g., `_data/info.json` is `{{data.info}}`).
+	pub data_dir: PathBuf,
+	/// The archetypes dir of `ssite new` (e.g., `archetypes/page.md`).
+	pub archetypes_dir: PathBuf,
+	/// `[source] ignore` patterns (gitignore syntax, relative to the content dir).
+	pub ignore_patterns: Vec<String>,
+	/// `[source] include` patterns, to include files otherwise excluded (e.g., `_redirects`).
+	pub include_patterns: Vec<String>,
+	/// `[source] keep` patterns of the dist files never removed by the clean (gitignore syntax, relative to the dist dir).
+	pub keep_patterns: Vec<String>,
+	pub url_style: UrlStyle,
+	pub on_collision: OnCollision,
+	/// `[source] template`, if the pages and frames are rendered with handlebars by default (default true).
+	pub template: bool,
+	/// The `[site]` values, available in templates as `{{site.some_name}}`.
+	pub site_values: Map<String, JsonValue>,
 	pub runner_configs: Option<Vec<RunnerConfig>>,
+	pub dev_config: DevConfig,
+	pub publish_config: Option<PublishConfig>,
+	pub sitemap_config: Option<SitemapConfig>,
+	pub feed_configs: Vec<FeedConfig>,
+	pub collection_configs: Vec<CollectionConfig>,
 }
 
 impl SiteConfig {
@@ -41,7 +70,7 @@ impl SiteConfig {
 			Some(runners) => Some(
 				runners
 					.into_iter()
-					.map(|(name, props)| RunnerConfig::from_value(name, &props))
+					.map(|(name, props)| RunnerConfig::from_value(name, props))
 					.collect::<Result<Vec<_>, _>>()?,
 			),
 			None => None,
@@ -55,20 +84,425 @@ impl SiteConfig {
 		let dist_dir = toml.deep_string(&["source", "dist_dir"])?;
 		let dist_dir = root_dir.join(Path::new(&dist_dir));
 		if !dist_dir.exists() {
-			// TODO: Handle error
-			let _ = create_dir_all(&dist_dir).expect(&f!("Cannot create dir {dist_dir:?}"));
+			create_dir_all(&dist_dir)?;
 		}
 		let dist_dir = dist_dir.canonicalize()?;
 
+		// the partials dir is relative to the content dir (does not have to exist)
+		let partials_dir = toml
+			.deep_string(&["source", "partials_dir"])
+			.unwrap_or_else(|_| s!(DEFAULT_PARTIALS_DIR));
+		let partials_dir = content_dir.join(partials_dir);
+
+		// the data dir is relative to the content dir (does not have to exist)
+		let data_dir = toml
+			.deep_string(&["source", "data_dir"])
+			.unwrap_or_else(|_| s!(DEFAULT_DATA_DIR));
+		let data_dir = content_dir.join(data_dir);
+
+		// the archetypes dir is relative to the root dir (not part of the content)
+		let archetypes_dir = toml
+			.deep_string(&["source", "archetypes_dir"])
+			.unwrap_or_else(|_| s!(DEFAULT_ARCHETYPES_DIR));
+		let archetypes_dir = root_dir.join(archetypes_dir);
+
+		let ignore_patterns = toml.deep_vec_string(&["source", "ignore"]).unwrap_or_default();
+		let include_patterns = toml.deep_vec_string(&["source", "include"]).unwrap_or_default();
+		let keep_patterns = toml.deep_vec_string(&["source", "keep"]).unwrap_or_default();
+
+		// guard - the dist dir is cleaned, so, it must not be (or contain) the source dirs
+		for (name, dir) in [
+			("root_dir", &root_dir),
+			("content_dir", &content_dir),
+			("partials_dir", &partials_dir),
+			("data_dir", &data_dir),
+			("archetypes_dir", &archetypes_dir),
+		] {
+			if dir.starts_with(&dist_dir) {
+				return Err(Error::InvalidConfigValue(
+					s!("source.dist_dir"),
+					f!("'{}' must not be or contain the {name} '{}'", dist_dir.display(), dir.display()),
+				));
+			}
+		}
+
+		let url_style = match toml.deep_str(&["source", "url_style"]) {
+			Ok(url_style) => UrlStyle::from_str(url_style).map_err(|_| {
+				Error::InvalidConfigValue(
+					s!("source.url_style"),
+					f!("'{url_style}' must be 'extensionless' | 'directory' | 'html'"),
+				)
+			})?,
+			Err(_) => UrlStyle::default(),
+		};
+
+		let on_collision = match toml.deep_str(&["source", "on_collision"]) {
+			Ok(on_collision) => OnCollision::from_str(on_collision).map_err(|_| {
+				Error::InvalidConfigValue(
+					s!("source.on_collision"),
+					f!("'{on_collision}' must be 'error' | 'html' | 'markdown'"),
+				)
+			})?,
+			Err(_) => OnCollision::default(),
+		};
+
+		let template = toml.deep_bool(&["source", "template"]).unwrap_or(true);
+
+		let site_values = match toml.get("site").cloned().map(toml_to_json) {
+			Some(JsonValue::Object(site_values)) => site_values,
+			_ => Map::new(),
+		};
+
+		let dev_config = DevConfig::from_value(&toml)?;
+
+		let publish_config = match toml.get("publish") {
+			Some(publish) => Some(PublishConfig::from_value(publish)?),
+			None => None,
+		};
+
+		let sitemap_config = match toml.get("sitemap") {
+			Some(sitemap) => Some(SitemapConfig::from_value(sitemap, &site_values)?),
+			None => None,
+		};
+
+		let feed_configs = match toml.get("feed").and_then(|v| v.as_array()) {
+			Some(feeds) => feeds
+				.iter()
+				.map(|feed| FeedConfig::from_value(feed, &site_values))
+				.collect::<Result<Vec<_>, _>>()?,
+			None => Vec::new(),
+		};
+
+		let collection_configs = match toml.get("collection").and_then(|v| v.as_array()) {
+			Some(collections) => collections
+				.iter()
+				.map(CollectionConfig::from_value)
+				.collect::<Result<Vec<_>, _>>()?,
+			None => Vec::new(),
+		};
+
 		Ok(SiteConfig {
 			root_dir: root_dir.to_path_buf(),
 			content_dir,
 			dist_dir,
+			partials_dir,
+			data_dir,
+			archetypes_dir,
+			ignore_patterns,
+			include_patterns,
+			keep_patterns,
+			url_style,
+			on_collision,
+			template,
+			site_values,
 			runner_configs,
+			dev_config,
+			publish_config,
+			sitemap_config,
+			feed_configs,
+			collection_configs,
 		})
 	}
 }
 
+// region:    --- UrlStyle
+
+/// The `[source] url_style`, i.e., the dist file of a page (e.g., `hello.md`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum UrlStyle {
+	/// `_site/hello` (served as `/hello`, e.g., S3 with content type)
+	#[default]
+	Extensionless,
+	/// `_site/hello/index.html` (served as `/hello/`, e.g., GitHub Pages, nginx)
+	Directory,
+	/// `_site/hello.html` (served as `/hello.html`)
+	Html,
+}
+
+// endregion: --- UrlStyle
+
+// region:    --- OnCollision
+
+/// The `[source] on_collision`, i.e., what to do when content files have the same dist file
+/// (e.g., `hello.md` and `hello.html`, or `README.md` and `index.html`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum OnCollision {
+	/// The build fails, with the colliding files named.
+	#[default]
+	Error,
+	/// The `.html` file is kept (e.g., `index.html` over `README.md`).
+	Html,
+	/// The `.md` file is kept (e.g., `README.md` over `index.html`).
+	Markdown,
+}
+
+// endregion: --- OnCollision
+
+// region:    --- DevConfig
+
+/// The `[dev]` section, for the `ssite dev` local http server.
+#[derive(Debug, Clone)]
+pub struct DevConfig {
+	pub host: String,
+	pub port: u16,
+}
+
+impl DevConfig {
+	pub fn from_value(toml: &Value) -> Result<DevConfig, Error> {
+		let host = match toml.deep_get(&["dev", "host"]) {
+			Some(Value::String(host)) => s!(host),
+			Some(host) => {
+				return Err(Error::InvalidConfigValue(
+					s!("dev.host"),
+					f!("'{host}' must be a string (e.g., \"127.0.0.1\")"),
+				))
+			}
+			None => s!(DEFAULT_DEV_HOST),
+		};
+		let port = match toml.deep_get(&["dev", "port"]) {
+			Some(Value::Integer(port)) => {
+				u16::try_from(*port).map_err(|ex| Error::InvalidConfigValue(s!("dev.port"), s!(ex)))?
+			}
+			Some(port) => {
+				return Err(Error::InvalidConfigValue(
+					s!("dev.port"),
+					f!("'{port}' must be an integer (e.g., 8080)"),
+				))
+			}
+			None => DEFAULT_DEV_PORT,
+		};
+
+		Ok(DevConfig { host, port })
+	}
+}
+
+// endregion: --- DevConfig
+
+// region:    --- PublishConfig
+
+/// The `[publish]` section, for the `ssite publish` S3 (or S3-compatible) sync.
+#[derive(Debug, Clone)]
+pub struct PublishConfig {
+	pub bucket_name: String,
+	/// Key prefix in the bucket (e.g., "site-a"), None for the bucket root.
+	pub bucket_root: Option<String>,
+	pub bucket_cred: BucketCred,
+	pub bucket_region: Option<String>,
+	/// Custom endpoint (e.g., "http://127.0.0.1:9000" for a local S3-compatible server).
+	pub bucket_endpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BucketCred {
+	/// The default AWS credential chain (environment variables, default profile, ...)
+	Default,
+	/// A named profile of the AWS config/credentials files
+	Profile(String),
+}
+
+impl PublishConfig {
+	pub fn from_value(toml: &Value) -> Result<PublishConfig, Error> {
+		// For now, only s3 (and s3 compatible) buckets are supported.
+		match toml.deep_str(&["bucket_type"]) {
+			Ok("s3") | Err(_) => (),
+			Ok(_) => return Err(Error::InvalidS3Config),
+		}
+
+		let bucket_name = toml.deep_string(&["bucket_name"]).map_err(|_| Error::InvalidS3Config)?;
+
+		let bucket_root = toml
+			.deep_string(&["bucket_root"])
+			.ok()
+			.map(|root| root.trim_matches('/').to_string())
+			.filter(|root| !root.is_empty());
+
+		let bucket_cred = match toml.deep_str(&["bucket_cred_type"]) {
+			Ok("profile") => {
+				let profile = toml.deep_string(&["bucket_cred_profile"]).map_err(|_| Error::InvalidS3Config)?;
+				BucketCred::Profile(profile)
+			}
+			Ok("default") | Err(_) => BucketCred::Default,
+			Ok(_) => return Err(Error::InvalidS3Config),
+		};
+
+		Ok(PublishConfig {
+			bucket_name,
+			bucket_root,
+			bucket_cred,
+			bucket_region: toml.deep_string(&["bucket_region"]).ok(),
+			bucket_endpoint: toml.deep_string(&["bucket_endpoint"]).ok(),
+		})
+	}
+}
+
+// endregion: --- PublishConfig
+
+// region:    --- SitemapConfig
+
+/// The `[sitemap]` section, for the generated `sitemap.xml` (and optional `robots.txt`).
+#[derive(Debug, Clone)]
+pub struct SitemapConfig {
+	/// The public site url, without the trailing `/` (e.g., "https://example.com").
+	pub base_url: String,
+	/// Url path patterns (gitignore syntax) of the pages to exclude (e.g., "/404", "/private/").
+	pub exclude: Vec<String>,
+	/// Also generate a `robots.txt` pointing to the sitemap.
+	pub robots: bool,
+}
+
+impl SitemapConfig {
+	/// The `base_url` is the `[sitemap] base_url`, or the `[site] base_url`.
+	pub fn from_value(toml: &Value, site_values: &Map<String, JsonValue>) -> Result<SitemapConfig, Error> {
+		let base_url = toml
+			.deep_string(&["base_url"])
+			.ok()
+			.or_else(|| site_values.get("base_url").and_then(|v| v.as_str()).map(|v| v.to_string()))
+			.ok_or_else(|| Error::MissingConfigProperty(s!("sitemap.base_url (or site.base_url)")))?;
+
+		Ok(SitemapConfig {
+			base_url: base_url.trim_end_matches('/').to_string(),
+			exclude: toml.deep_vec_string(&["exclude"]).unwrap_or_default(),
+			robots: toml.deep_bool(&["robots"]).unwrap_or(false),
+		})
+	}
+}
+
+// endregion: --- SitemapConfig
+
+// region:    --- FeedConfig
+
+/// A `[[feed]]` section, for an Atom and/or RSS feed of the dated pages of a content dir.
+#[derive(Debug, Clone)]
+pub struct FeedConfig {
+	/// The pages dir, relative to the content dir (e.g., "blog").
+	pub dir: String,
+	/// The feed title (default `[site] title`).
+	pub title: String,
+	pub description: Option<String>,
+	/// The default author of the entries (default `[site] author`).
+	pub author: Option<String>,
+	/// The public site url, without the trailing `/` (default `[site] base_url`).
+	pub base_url: String,
+	/// Atom file, relative to the dist dir (default "{dir}/atom.xml" if no rss).
+	pub atom: Option<String>,
+	/// RSS 2.0 file, relative to the dist dir.
+	pub rss: Option<String>,
+	/// The max number of entries (most recent first).
+	pub limit: usize,
+}
+
+impl FeedConfig {
+	pub fn from_value(toml: &Value, site_values: &Map<String, JsonValue>) -> Result<FeedConfig, Error> {
+		let site_value = |name: &str| site_values.get(name).and_then(|v| v.as_str()).map(|v| v.to_string());
+
+		let dir = toml.deep_string(&["dir"]).map_err(|_| Error::MissingConfigProperty(s!("feed.dir")))?;
+		let dir = dir.trim_matches('/').to_string();
+
+		let base_url = toml
+			.deep_string(&["base_url"])
+			.ok()
+			.or_else(|| site_value("base_url"))
+			.ok_or_else(|| Error::MissingConfigProperty(f!("feed.base_url (or site.base_url) for feed '{dir}'")))?;
+
+		let rss = toml.deep_string(&["rss"]).ok();
+		let atom = match toml.deep_string(&["atom"]).ok() {
+			Some(atom) => Some(atom),
+			None if rss.is_none() => Some(f!("{dir}/atom.xml")),
+			None => None,
+		};
+
+		let limit = match toml.deep_i64(&["limit"]) {
+			Ok(limit) => usize::try_from(limit).map_err(|ex| Error::InvalidConfigValue(s!("feed.limit"), s!(ex)))?,
+			Err(_) => DEFAULT_FEED_LIMIT,
+		};
+
+		Ok(FeedConfig {
+			title: toml.deep_string(&["title"]).ok().or_else(|| site_value("title")).unwrap_or_else(|| dir.clone()),
+			description: toml.deep_string(&["description"]).ok(),
+			author: toml.deep_string(&["author"]).ok().or_else(|| site_value("author")),
+			base_url: base_url.trim_end_matches('/').to_string(),
+			atom,
+			rss,
+			limit,
+			dir,
+		})
+	}
+}
+
+// endregion: --- FeedConfig
+
+// region:    --- CollectionConfig
+
+/// A `[[collection]]` section, the pages of a content dir listed with `INCLUDE_LIST(name)`,
+/// with the optional pagination (`/{dir}/page/2`) and tag (`/{dir}/tags/{tag}`) pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionConfig {
+	/// The name of the `INCLUDE_LIST(name)` marker (default the last dir name).
+	pub name: String,
+	/// The pages dir, relative to the content dir (e.g., "blog").
+	pub dir: String,
+	/// The title of the generated pages (default the name).
+	pub title: String,
+	pub sort: CollectionSort,
+	/// Number of pages per list page (no pagination if none).
+	pub per_page: Option<usize>,
+	/// Generate the tag pages.
+	pub tags: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum CollectionSort {
+	/// Most recent first (undated pages last).
+	#[default]
+	Date,
+	/// Alphabetical.
+	Title,
+}
+
+impl CollectionConfig {
+	pub fn from_value(toml: &Value) -> Result<CollectionConfig, Error> {
+		let dir = toml.deep_string(&["dir"]).map_err(|_| Error::MissingConfigProperty(s!("collection.dir")))?;
+		let dir = dir.trim_matches('/').to_string();
+
+		let name = match toml.deep_string(&["name"]) {
+			Ok(name) => name,
+			Err(_) => dir.rsplit('/').next().unwrap_or_default().to_string(),
+		};
+
+		let sort = match toml.deep_str(&["sort"]) {
+			Ok(sort) => CollectionSort::from_str(sort).map_err(|_| {
+				Error::InvalidConfigValue(s!("collection.sort"), f!("'{sort}' must be 'date' | 'title'"))
+			})?,
+			Err(_) => CollectionSort::default(),
+		};
+
+		let per_page = match toml.deep_i64(&["per_page"]) {
+			Ok(per_page) if per_page > 0 => Some(per_page as usize),
+			Ok(per_page) => {
+				return Err(Error::InvalidConfigValue(
+					s!("collection.per_page"),
+					f!("{per_page} must be greater than 0"),
+				))
+			}
+			Err(_) => None,
+		};
+
+		Ok(CollectionConfig {
+			title: toml.deep_string(&["title"]).unwrap_or_else(|_| name.clone()),
+			name,
+			dir,
+			sort,
+			per_page,
+			tags: toml.deep_bool(&["tags"]).unwrap_or(false),
+		})
+	}
+}
+
+// endregion: --- CollectionConfig
+
 // region:    --- RunnerConfig
 
 #[derive(Debug)]
@@ -107,15 +541,11 @@ impl RunnerConfig {
 				run_modes
 			}
 		};
-		// let mrun_on: Vec<RunMode> = Vec::new();
-
-		// let default_run_modes =
-
 		Ok(RunnerConfig {
 			name: runner_name.to_string(),
 			cwd: toml.deep_string(&["cwd"]).ok(),
 			cmd: toml.deep_string(&["cmd"])?,
-			args: toml.deep_vec_string(&["args"]).ok(),
+			args,
 			watch_args,
 			run_modes,
 		})
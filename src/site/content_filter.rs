@@ -0,0 +1,97 @@
+//! Which files of the content dir are part of the site.
+//!
+//! Excluded (unless matched by a `[source] include` pattern):
+//! - the underscore-prefixed files and dirs (e.g., `_partials/`, `_drafts/`), except the `_frame.html` frames.
+//! - the `[source] ignore` patterns (gitignore syntax, relative to the content dir).
+//! - the patterns of the `.ssiteignore` file of the root dir (gitignore semantics, relative to the root dir).
+
+use crate::consts::{FRAME, SSITEIGNORE_FILE_NAME};
+use crate::{f, Error};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug)]
+pub struct ContentFilter {
+	root_dir: PathBuf,
+	include: Gitignore,
+	ignore: Gitignore,
+	ssiteignore: Gitignore,
+}
+
+/// Makers
+impl ContentFilter {
+	pub fn new(root_dir: &Path, content_dir: &Path, ignore: &[String], include: &[String]) -> Result<Self, Error> {
+		let ssiteignore_file = root_dir.join(SSITEIGNORE_FILE_NAME);
+		let ssiteignore = if ssiteignore_file.is_file() {
+			let (ssiteignore, err) = Gitignore::new(&ssiteignore_file);
+			if let Some(err) = err {
+				return Err(Error::InvalidConfigValue(ssiteignore_file.display().to_string(), err.to_string()));
+			}
+			ssiteignore
+		} else {
+			Gitignore::empty()
+		};
+
+		Ok(ContentFilter {
+			root_dir: root_dir.to_path_buf(),
+			include: build_gitignore(content_dir, "source.include", include)?,
+			ignore: build_gitignore(content_dir, "source.ignore", ignore)?,
+			ssiteignore,
+		})
+	}
+}
+
+impl ContentFilter {
+	/// Determine if the path (file or dir) of the content dir is part of the site.
+	pub fn is_included(&self, content_dir: &Path, path: &Path, is_dir: bool) -> bool {
+		let rel_path = match path.strip_prefix(content_dir) {
+			Ok(rel_path) => rel_path,
+			Err(_) => return false,
+		};
+		// the content dir itself
+		if rel_path.as_os_str().is_empty() {
+			return true;
+		}
+
+		if self.include.matched_path_or_any_parents(rel_path, is_dir).is_ignore() {
+			return true;
+		}
+
+		// Note: The .ssiteignore matcher only applies to the paths of the root dir (panics otherwise).
+		let ssiteignored =
+			path.starts_with(&self.root_dir) && self.ssiteignore.matched_path_or_any_parents(path, is_dir).is_ignore();
+
+		!has_underscore_prefix(rel_path)
+			&& !self.ignore.matched_path_or_any_parents(rel_path, is_dir).is_ignore()
+			&& !ssiteignored
+	}
+}
+
+// region:    --- Utils
+
+pub(super) fn build_gitignore(dir: &Path, name: &str, patterns: &[String]) -> Result<Gitignore, Error> {
+	let mut builder = GitignoreBuilder::new(dir);
+	for pattern in patterns {
+		builder
+			.add_line(None, pattern)
+			.map_err(|ex| Error::InvalidConfigValue(f!("{name} '{pattern}'"), ex.to_string()))?;
+	}
+	builder.build().map_err(|ex| Error::InvalidConfigValue(name.to_string(), ex.to_string()))
+}
+
+/// True if one of the path components starts with `_` (the `_frame.html` frames excepted).
+fn has_underscore_prefix(rel_path: &Path) -> bool {
+	rel_path.components().any(|c| match c {
+		Component::Normal(name) => {
+			let name = name.to_string_lossy();
+			name.starts_with('_') && name != FRAME
+		}
+		_ => false,
+	})
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_content_filter.rs"]
+mod tests;
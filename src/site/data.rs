@@ -0,0 +1,157 @@
+//! Global data files of the data dir (e.g., `_data/`), available to the pages and frames as `{{data.name}}`.
+//!
+//! The `.json`, `.toml`, and `.yaml` (or `.yml`) files are named by their path without extension
+//! (e.g., `_data/info.json` is `{{data.info}}`, and `_data/nav/main.yaml` is `{{data.nav.main}}`).
+
+use crate::prelude::*;
+use crate::utils::lower_case;
+use crate::utils::toml::toml_to_json;
+use crate::utils::yaml::yaml_to_json;
+use pathdiff::diff_paths;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+use walkdir::WalkDir;
+use yaml_rust::YamlLoader;
+
+static EXPR_RX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)\{\{(.*?)\}\}").unwrap());
+static DATA_RX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bdata\b(\.\[?([\w-]+))?").unwrap());
+
+#[derive(Debug)]
+pub struct SiteData {
+	data_dir: PathBuf,
+	inner: RwLock<Arc<DataValues>>,
+}
+
+#[derive(Debug, Default)]
+struct DataValues {
+	values: Map<String, Value>,
+	/// data file -> top level data name (e.g., `_data/nav/main.yaml` is "nav")
+	files: Vec<(PathBuf, String)>,
+}
+
+/// Makers
+impl SiteData {
+	pub fn new(data_dir: &Path) -> Result<Self> {
+		let data = SiteData {
+			data_dir: data_dir.to_path_buf(),
+			inner: RwLock::new(Arc::default()),
+		};
+		data.reload()?;
+		Ok(data)
+	}
+}
+
+impl SiteData {
+	/// (Re)load all of the data files of the data dir.
+	pub fn reload(&self) -> Result<()> {
+		let mut data = DataValues::default();
+
+		if self.data_dir.is_dir() {
+			for entry in WalkDir::new(&self.data_dir)
+				.sort_by_file_name()
+				.into_iter()
+				.filter_map(|e| e.ok().filter(|e| e.path().is_file()))
+			{
+				let file = entry.path();
+				let value = match parse_data_file(file)? {
+					Some(value) => value,
+					None => continue,
+				};
+				let name = match diff_paths(file.with_extension(""), &self.data_dir) {
+					Some(name) => name.to_string_lossy().replace('\\', "/"),
+					None => continue,
+				};
+				let names: Vec<&str> = name.split('/').collect();
+				insert_value(&mut data.values, &names, value)
+					.map_err(|ex| Error::DataFileError(f!("{}: {ex}", file.display())))?;
+				data.files.push((file.to_path_buf(), names[0].to_string()));
+			}
+		}
+
+		*self.inner.write().unwrap() = Arc::new(data);
+
+		Ok(())
+	}
+
+	/// The data values (the `data` of the templates).
+	pub fn values(&self) -> Map<String, Value> {
+		self.inner.read().unwrap().values.clone()
+	}
+
+	/// The data files used by a template content, i.e., with a `data.name` reference in a handlebars expression
+	/// (e.g., `{{data.info.version}}`). A `data` reference without a name (e.g., `{{#each data}}`, or
+	/// `{{lookup data "info"}}`) might be any of them, so, all of the data files are used.
+	pub fn used_files(&self, content: &str) -> Vec<PathBuf> {
+		let inner = self.inner.read().unwrap();
+		if inner.files.is_empty() || !content.contains("data") {
+			return Vec::new();
+		}
+
+		let mut names: Vec<&str> = Vec::new();
+		for expr in EXPR_RX.captures_iter(content).filter_map(|c| c.get(1)) {
+			for caps in DATA_RX.captures_iter(expr.as_str()) {
+				match caps.get(2) {
+					Some(name) => names.push(name.as_str()),
+					None => return inner.files.iter().map(|(file, _)| file.to_path_buf()).collect(),
+				}
+			}
+		}
+
+		inner
+			.files
+			.iter()
+			.filter(|(_, name)| names.contains(&name.as_str()))
+			.map(|(file, _)| file.to_path_buf())
+			.collect()
+	}
+}
+
+// region:    --- Utils
+
+/// Parse a data file per its extension. Returns None if not a data file.
+fn parse_data_file(file: &Path) -> Result<Option<Value>> {
+	let ext = lower_case(file.extension());
+	let parse_error = |ex: String| Error::DataFileError(f!("{}: {ex}", file.display()));
+
+	let value = match ext.as_deref() {
+		Some("json") => serde_json::from_str(&fs::read_to_string(file)?).map_err(|ex| parse_error(s!(ex)))?,
+		Some("toml") => {
+			let toml: toml::Value = toml::from_str(&fs::read_to_string(file)?).map_err(|ex| parse_error(s!(ex)))?;
+			toml_to_json(toml)
+		}
+		Some("yaml" | "yml") => {
+			let docs = YamlLoader::load_from_str(&fs::read_to_string(file)?).map_err(|ex| parse_error(s!(ex)))?;
+			docs.into_iter().next().map(yaml_to_json).unwrap_or(Value::Null)
+		}
+		_ => return Ok(None),
+	};
+
+	Ok(Some(value))
+}
+
+/// Insert the value at the names path (e.g., ["nav", "main"]), creating the intermediate objects.
+fn insert_value(values: &mut Map<String, Value>, names: &[&str], value: Value) -> core::result::Result<(), String> {
+	match names {
+		[] => Err(s!("empty data name")),
+		[name] => match values.get(*name) {
+			Some(_) => Err(f!("data name '{name}' already defined")),
+			None => {
+				values.insert(name.to_string(), value);
+				Ok(())
+			}
+		},
+		[name, rest @ ..] => match values.entry(name.to_string()).or_insert_with(|| Value::Object(Map::new())) {
+			Value::Object(values) => insert_value(values, rest, value),
+			_ => Err(f!("data name '{name}' already defined")),
+		},
+	}
+}
+
+// endregion: --- Utils
+
+#[cfg(test)]
+#[path = "../_tests/tests_site_data.rs"]
+mod tests;
@@ -1,20 +1,59 @@
+pub use self::collections::{Collection, CollectionItem, Collections};
 use self::config::{RunnerConfig, SiteConfig};
+use self::content_filter::{build_gitignore, ContentFilter};
+use self::data::SiteData;
+pub use self::config::{
+	BucketCred, CollectionConfig, CollectionSort, DevConfig, FeedConfig, OnCollision, PublishConfig, SitemapConfig,
+	UrlStyle,
+};
+use self::templates::Templates;
+use crate::consts::{CONFIG_FILE_NAME, SSITE_DIR};
+use crate::utils::{content_type, is_extensionless_page};
 use crate::Error;
+use ignore::gitignore::Gitignore;
+use serde_json::{Map, Value as JsonValue};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, RwLock};
 use strum_macros::EnumString;
-use toml::Value;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 use walkdir::{DirEntry, WalkDir};
 
+mod collections;
 mod config;
+mod content_filter;
+mod data;
+mod templates;
 
 #[derive(Debug)]
 pub struct Site {
 	content_dir: PathBuf,
 	dist_dir: PathBuf,
 	root_dir: PathBuf,
+	partials_dir: PathBuf,
+	data_dir: PathBuf,
+	archetypes_dir: PathBuf,
+	content_filter: ContentFilter,
+	/// The `[source] keep` dist files matcher.
+	dist_keep: Gitignore,
+	url_style: UrlStyle,
+	on_collision: OnCollision,
+	template: bool,
+	site_values: Map<String, JsonValue>,
+	build_time: String,
+	templates: Templates,
+	data: SiteData,
+	run_mode: RunMode,
 	runners: Option<Vec<Runner>>,
+	dev_config: DevConfig,
+	publish_config: Option<PublishConfig>,
+	sitemap_config: Option<SitemapConfig>,
+	feed_configs: Vec<FeedConfig>,
+	collection_configs: Vec<CollectionConfig>,
+	/// The collections of this build (loaded by the generation, and refreshed in watch mode).
+	collections: RwLock<Arc<Collections>>,
 }
 
 #[derive(Debug, Clone)]
@@ -54,7 +93,7 @@ impl Runner {
 	}
 
 	pub fn has_run_mode(&self, run_mode: &RunMode) -> bool {
-		self.run_on.contains(&run_mode)
+		self.run_on.contains(run_mode)
 	}
 
 	pub fn get_build_command(&self, root_dir: &Path) -> Command {
@@ -67,7 +106,7 @@ impl Runner {
 
 	pub fn get_watch_command(&self, root_dir: &Path) -> Command {
 		let mut cmd = self.get_base_cmd(root_dir);
-		if let Some(args) = self.watch_args.as_ref().or_else(|| self.args.as_ref()) {
+		if let Some(args) = self.watch_args.as_ref().or(self.args.as_ref()) {
 			cmd.args(args);
 		}
 		cmd
@@ -90,13 +129,48 @@ impl Site {
 	pub fn from_dir(dir: &Path) -> Result<Self, Error> {
 		let config = SiteConfig::from_dir(dir)?;
 		let runners = config.runner_configs.map(|v| v.into_iter().map(|v| v.into()).collect());
+		let templates = Templates::new(&config.partials_dir)?;
+		let data = SiteData::new(&config.data_dir)?;
+		let content_filter = ContentFilter::new(
+			&config.root_dir,
+			&config.content_dir,
+			&config.ignore_patterns,
+			&config.include_patterns,
+		)?;
+		let dist_keep = build_gitignore(&config.dist_dir, "source.keep", &config.keep_patterns)?;
+		let build_time = OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_default();
 		Ok(Site {
 			root_dir: config.root_dir,
 			content_dir: config.content_dir,
 			dist_dir: config.dist_dir,
+			partials_dir: config.partials_dir,
+			data_dir: config.data_dir,
+			archetypes_dir: config.archetypes_dir,
+			content_filter,
+			dist_keep,
+			url_style: config.url_style,
+			on_collision: config.on_collision,
+			template: config.template,
+			site_values: config.site_values,
+			build_time,
+			templates,
+			data,
+			run_mode: RunMode::Build,
 			runners,
+			dev_config: config.dev_config,
+			publish_config: config.publish_config,
+			sitemap_config: config.sitemap_config,
+			feed_configs: config.feed_configs,
+			collection_configs: config.collection_configs,
+			collections: RwLock::new(Arc::new(Collections::default())),
 		})
 	}
+
+	/// Set the run mode (default `Build`). In `Dev`, the draft and future pages are rendered.
+	pub fn with_run_mode(mut self, run_mode: RunMode) -> Self {
+		self.run_mode = run_mode;
+		self
+	}
 }
 
 impl Site {
@@ -111,12 +185,86 @@ impl Site {
 		&self.dist_dir
 	}
 
+	pub fn partials_dir(&self) -> &Path {
+		&self.partials_dir
+	}
+
+	pub fn data_dir(&self) -> &Path {
+		&self.data_dir
+	}
+
+	pub fn archetypes_dir(&self) -> &Path {
+		&self.archetypes_dir
+	}
+
+	pub fn url_style(&self) -> UrlStyle {
+		self.url_style
+	}
+
+	pub fn on_collision(&self) -> OnCollision {
+		self.on_collision
+	}
+
+	/// If the pages and frames are rendered with handlebars, unless their front matter `template` says otherwise.
+	pub fn template(&self) -> bool {
+		self.template
+	}
+
+	/// The `[site]` values of the config
+	pub fn site_values(&self) -> &Map<String, JsonValue> {
+		&self.site_values
+	}
+
+	/// The time (RFC3339) this site was loaded (i.e., build start)
+	pub fn build_time(&self) -> &str {
+		&self.build_time
+	}
+
+	pub fn templates(&self) -> &Templates {
+		&self.templates
+	}
+
+	pub fn data(&self) -> &SiteData {
+		&self.data
+	}
+
+	pub fn run_mode(&self) -> &RunMode {
+		&self.run_mode
+	}
+
 	pub fn runners(&self) -> Option<&Vec<Runner>> {
 		self.runners.as_ref()
 	}
 
-	/// Return the files entries of the content folder
-	#[allow(unused)]
+	pub fn dev_config(&self) -> &DevConfig {
+		&self.dev_config
+	}
+
+	pub fn publish_config(&self) -> Option<&PublishConfig> {
+		self.publish_config.as_ref()
+	}
+
+	pub fn sitemap_config(&self) -> Option<&SitemapConfig> {
+		self.sitemap_config.as_ref()
+	}
+
+	pub fn feed_configs(&self) -> &[FeedConfig] {
+		&self.feed_configs
+	}
+
+	pub fn collection_configs(&self) -> &[CollectionConfig] {
+		&self.collection_configs
+	}
+
+	pub fn collections(&self) -> Arc<Collections> {
+		self.collections.read().unwrap().clone()
+	}
+
+	pub fn set_collections(&self, collections: Collections) {
+		*self.collections.write().unwrap() = Arc::new(collections);
+	}
+
+	/// Return the files entries of the dist folder
 	pub fn dist_entries(&self) -> impl Iterator<Item = DirEntry> {
 		WalkDir::new(self.dist_dir())
 			.into_iter()
@@ -129,7 +277,7 @@ impl Site {
 		WalkDir::new(self.content_dir())
 			.into_iter()
 			.filter_entry(|e| match e.path().canonicalize() {
-				Ok(path) => self.valid_content_path(&path),
+				Ok(path) => self.valid_content_path(&path) && self.is_included_path(&path, e.file_type().is_dir()),
 				Err(_) => false,
 			})
 			.filter_map(|e| e.ok())
@@ -137,7 +285,41 @@ impl Site {
 	}
 
 	pub fn valid_content_path(&self, path: &Path) -> bool {
-		!path.starts_with(self.dist_dir()) && !path.ends_with("ssite.toml")
+		!path.starts_with(self.dist_dir())
+			&& !path.starts_with(self.partials_dir())
+			&& !path.starts_with(self.data_dir())
+			&& !path.starts_with(self.root_dir().join(SSITE_DIR))
+			&& !path.ends_with(CONFIG_FILE_NAME)
+	}
+
+	/// Determine if the content path is part of the site (see `[source] ignore` and `include`, and `.ssiteignore`).
+	/// Underscore-prefixed files and dirs are excluded by default (except the `_frame.html` frames).
+	pub fn is_included_path(&self, path: &Path, is_dir: bool) -> bool {
+		self.content_filter.is_included(&self.content_dir, path, is_dir)
+	}
+
+	/// Determine if the dist file must never be removed by the clean (see `[source] keep`).
+	pub fn is_kept_dist_file(&self, file: &Path) -> bool {
+		// Note: The gitignore matcher only applies to the paths of the dist dir (panics otherwise).
+		file.starts_with(self.dist_dir()) && self.dist_keep.matched_path_or_any_parents(file, false).is_ignore()
+	}
+
+	/// Return the public url (root relative) of a dist file.
+	/// `index.html` files are served as their directory (e.g., `_site/sub-dir/index.html` is `/sub-dir/`).
+	pub fn dist_url(&self, dist_file: &Path) -> Option<String> {
+		let rel_path = dist_file.strip_prefix(self.dist_dir()).ok()?;
+		let rel_path = rel_path.to_string_lossy().replace('\\', "/");
+		let url = match rel_path.strip_suffix("index.html") {
+			Some(dir) if dir.is_empty() || dir.ends_with('/') => dir.to_string(),
+			_ => rel_path,
+		};
+		Some(format!("/{url}"))
+	}
+
+	/// The content type of a dist file (the extension-less files are html only for the pages, see `url_style`).
+	pub fn dist_content_type(&self, dist_file: &Path) -> String {
+		let page = is_extensionless_page(dist_file, &self.dist_dir, &self.content_dir, self.url_style);
+		content_type(dist_file, page)
 	}
 }
 
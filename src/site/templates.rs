@@ -0,0 +1,105 @@
+//! Handlebars engine used to render the pages and frames, with the partials of the partials dir.
+
+use crate::prelude::*;
+use handlebars::Handlebars;
+use pathdiff::diff_paths;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+use walkdir::WalkDir;
+
+static PARTIAL_RX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"\{\{~?#?>\s*"?([^\s"}~]+)"#).unwrap());
+
+#[derive(Debug)]
+pub struct Templates {
+	partials_dir: PathBuf,
+	hbs: RwLock<Handlebars<'static>>,
+	/// partial name -> partial file
+	partials: RwLock<BTreeMap<String, PathBuf>>,
+}
+
+/// Makers
+impl Templates {
+	pub fn new(partials_dir: &Path) -> Result<Self> {
+		let templates = Templates {
+			partials_dir: partials_dir.to_path_buf(),
+			hbs: RwLock::new(Handlebars::new()),
+			partials: RwLock::new(BTreeMap::new()),
+		};
+		templates.reload_partials()?;
+		Ok(templates)
+	}
+}
+
+impl Templates {
+	/// (Re)register all of the partials from the partials dir.
+	/// The partial name is the file path relative to the partials dir, without extension
+	/// (e.g., `_partials/nav/main.html` is `{{> nav/main}}`).
+	pub fn reload_partials(&self) -> Result<()> {
+		let mut hbs = Handlebars::new();
+		let mut partials = BTreeMap::new();
+
+		if self.partials_dir.is_dir() {
+			for entry in WalkDir::new(&self.partials_dir)
+				.into_iter()
+				.filter_map(|e| e.ok().filter(|e| e.path().is_file()))
+			{
+				let file = entry.path();
+				let name = match diff_paths(file.with_extension(""), &self.partials_dir) {
+					Some(name) => name.to_string_lossy().replace('\\', "/"),
+					None => continue,
+				};
+				let content = fs::read_to_string(file)?;
+				hbs.register_partial(&name, content)
+					.map_err(|ex| Error::TemplateError(f!("partial {}: {ex}", file.display())))?;
+				partials.insert(name, file.to_path_buf());
+			}
+		}
+
+		*self.hbs.write().unwrap() = hbs;
+		*self.partials.write().unwrap() = partials;
+
+		Ok(())
+	}
+
+	/// The partial files used by a template content (e.g., `{{> nav/main}}`), and by these partials (sorted).
+	/// A dynamic partial (e.g., `{{> (name)}}`) might be any of them, so, all of the partials are used.
+	pub fn used_partials(&self, content: &str) -> Vec<PathBuf> {
+		let partials = self.partials.read().unwrap();
+		if partials.is_empty() || !content.contains('>') {
+			return Vec::new();
+		}
+
+		let mut used: BTreeMap<&str, &Path> = BTreeMap::new();
+		let mut contents = vec![content.to_string()];
+		while let Some(content) = contents.pop() {
+			for name in PARTIAL_RX.captures_iter(&content).filter_map(|c| c.get(1)).map(|m| m.as_str()) {
+				if name.starts_with('(') {
+					return partials.values().cloned().collect();
+				}
+				if let Some((name, file)) = partials.get_key_value(name) {
+					if used.insert(name, file).is_none() {
+						contents.push(fs::read_to_string(file).unwrap_or_default());
+					}
+				}
+			}
+		}
+
+		let mut files: Vec<PathBuf> = used.into_values().map(|file| file.to_path_buf()).collect();
+		files.sort();
+		files
+	}
+
+	/// Render the template string with the context data.
+	/// `file` is only used for the error message.
+	pub fn render(&self, file: &Path, template: &str, data: &Value) -> Result<String> {
+		self.hbs
+			.read()
+			.unwrap()
+			.render_template(template, data)
+			.map_err(|ex| Error::TemplateError(f!("{}: {ex}", file.display())))
+	}
+}
@@ -1,9 +1,13 @@
+use crate::consts::HTML_CONTENT_TYPE;
+use crate::site::UrlStyle;
 use crate::Error;
+use md5::{Digest, Md5};
 use pathdiff::diff_paths;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 
 pub mod toml;
+pub mod yaml;
 
 mod x_string;
 
@@ -12,7 +16,7 @@ mod x_string;
 pub use self::x_string::*;
 
 pub fn lower_case(ostr: Option<&OsStr>) -> Option<String> {
-	ostr.and_then(|s| s.to_str()).and_then(|s| Some(s.to_lowercase()))
+	ostr.and_then(|s| s.to_str()).map(|s| s.to_lowercase())
 }
 
 pub fn assert_valid_dir(path: &Path) -> Result<(), Error> {
@@ -32,6 +36,110 @@ pub fn rebase_path(src_base_dir: &Path, src_file: &Path, dst_base_dir: &Path) ->
 	}
 }
 
+/// Content type from the file extension (binary if unknown).
+/// The `extensionless_page` files are the html pages of the `extensionless` url style (see `is_extensionless_page`).
+pub fn content_type(file: &Path, extensionless_page: bool) -> String {
+	if extensionless_page {
+		return HTML_CONTENT_TYPE.to_string();
+	}
+	let mime = mime_guess::from_path(file).first_or_octet_stream();
+	if mime == mime_guess::mime::TEXT_HTML {
+		HTML_CONTENT_TYPE.to_string()
+	} else {
+		mime.to_string()
+	}
+}
+
+/// Determine if the extension-less dist file is a page of the `extensionless` url style,
+/// i.e., not a content file copied as is (e.g., `CNAME`, `LICENSE`, `_redirects`).
+pub fn is_extensionless_page(dist_file: &Path, dist_dir: &Path, content_dir: &Path, url_style: UrlStyle) -> bool {
+	url_style == UrlStyle::Extensionless
+		&& dist_file.extension().is_none()
+		&& !rebase_path(dist_dir, dist_file, content_dir)
+			.map(|src_file| src_file.is_file())
+			.unwrap_or(false)
+}
+
+/// Escape the xml special characters of a text or attribute value.
+pub fn xml_escape(value: &str) -> String {
+	let mut res = String::with_capacity(value.len());
+	for c in value.chars() {
+		match c {
+			'&' => res.push_str("&amp;"),
+			'<' => res.push_str("&lt;"),
+			'>' => res.push_str("&gt;"),
+			'"' => res.push_str("&quot;"),
+			'\'' => res.push_str("&apos;"),
+			_ => res.push(c),
+		}
+	}
+	res
+}
+
+/// Lower case hex md5 of the data (same as the S3 ETag of single part uploads).
+pub fn md5_hex(data: &[u8]) -> String {
+	Md5::digest(data).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Determine if the link has a url scheme (e.g., `https:`, `mailto:`, `data:`).
+pub fn has_url_scheme(link: &str) -> bool {
+	match link.find(':') {
+		Some(idx) => {
+			let scheme = &link[..idx];
+			!scheme.is_empty()
+				&& scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+				&& scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+		}
+		None => false,
+	}
+}
+
+/// Decode the `%XX` escapes of an url (e.g., "my%20page" is "my page").
+pub fn percent_decode(value: &str) -> String {
+	let bytes = value.as_bytes();
+	let mut res: Vec<u8> = Vec::with_capacity(bytes.len());
+	let mut i = 0;
+	while i < bytes.len() {
+		let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+		match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+			(b'%', Some(byte)) => {
+				res.push(byte);
+				i += 3;
+			}
+			(byte, _) => {
+				res.push(byte);
+				i += 1;
+			}
+		}
+	}
+	String::from_utf8_lossy(&res).to_string()
+}
+
+/// The relative url from a page url to another root relative url
+/// (e.g., from "/blog/hello" to "/sub-dir/" is "../sub-dir/", and to "/blog/" is "./").
+pub fn relative_url(from_url: &str, to_url: &str) -> String {
+	let from_dir = &from_url[..from_url.rfind('/').map(|idx| idx + 1).unwrap_or(0)];
+	let from_dir: Vec<&str> = from_dir.split('/').filter(|s| !s.is_empty()).collect();
+	let to_segments: Vec<&str> = to_url.split('/').filter(|s| !s.is_empty()).collect();
+	let to_is_dir = to_url.ends_with('/');
+
+	// Note: The last segment of a file url is not a dir.
+	let to_dir_len = if to_is_dir { to_segments.len() } else { to_segments.len().saturating_sub(1) };
+	let common = from_dir.iter().zip(to_segments[..to_dir_len].iter()).take_while(|(a, b)| a == b).count();
+
+	let mut url = "../".repeat(from_dir.len() - common);
+	url.push_str(&to_segments[common..].join("/"));
+	if to_is_dir && common < to_segments.len() {
+		url.push('/');
+	}
+
+	if url.is_empty() {
+		"./".to_string()
+	} else {
+		url.replace(' ', "%20")
+	}
+}
+
 #[cfg(test)]
 #[path = "../_tests/tests_utils.rs"]
 mod tests;
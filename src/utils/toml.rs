@@ -1,5 +1,4 @@
 use crate::prelude::*;
-use std::collections::HashSet;
 use toml::Value;
 
 // region:    --- Another Approach (one trait for all types)
@@ -8,6 +7,8 @@ pub trait DeepGet {
 	fn deep_string(&self, arr: &[&str]) -> Result<String>;
 	fn deep_str<'v>(&'v self, arr: &[&str]) -> Result<&'v str>;
 	fn deep_vec_string(&self, arr: &[&str]) -> Result<Vec<String>>;
+	fn deep_i64(&self, arr: &[&str]) -> Result<i64>;
+	fn deep_bool(&self, arr: &[&str]) -> Result<bool>;
 }
 
 impl DeepGet for Value {
@@ -15,10 +16,7 @@ impl DeepGet for Value {
 		let mut value: &Value = self;
 
 		for name in arr {
-			value = match value.get(name) {
-				Some(v) => v,
-				None => return None,
-			}
+			value = value.get(name)?
 		}
 
 		Some(value)
@@ -36,7 +34,7 @@ impl DeepGet for Value {
 			Some(v_arr) => {
 				// FIXME: Should return error cannot be as_str()
 				let v = v_arr
-					.into_iter()
+					.iter()
 					.map(|v| v.as_str().map(|v| v.to_string()).unwrap_or("".to_string()))
 					.collect();
 				Ok(v)
@@ -51,32 +49,38 @@ impl DeepGet for Value {
 			.and_then(|v| v.as_str())
 			.ok_or_else(|| Error::TomlMissingValue(arr.join(".").to_string()))
 	}
-}
-// endregion: --- Another Approach (one trait for all types)
 
-// region:    --- Old Utilities
-pub fn toml_as_string(root: &Value, arr: &[&str]) -> Result<String> {
-	toml_as_option_string(&root, arr).ok_or_else(|| Error::MissingConfigProperty(arr.join(".").to_string()))
-}
+	fn deep_i64(&self, arr: &[&str]) -> Result<i64> {
+		self
+			.deep_get(arr)
+			.and_then(|v| v.as_integer())
+			.ok_or_else(|| Error::TomlMissingValue(arr.join(".").to_string()))
+	}
 
-pub fn toml_as_option_string(root: &Value, arr: &[&str]) -> Option<String> {
-	let value = toml_as_option_value(root, arr)?;
-	match value.as_str() {
-		Some(str) => Some(str.to_owned()),
-		None => None,
+	fn deep_bool(&self, arr: &[&str]) -> Result<bool> {
+		self
+			.deep_get(arr)
+			.and_then(|v| v.as_bool())
+			.ok_or_else(|| Error::TomlMissingValue(arr.join(".").to_string()))
 	}
 }
+// endregion: --- Another Approach (one trait for all types)
 
-pub fn toml_as_option_value<'v>(root: &'v Value, arr: &[&str]) -> Option<&'v Value> {
-	let mut value: &Value = root;
+// region:    --- Json
 
-	for name in arr {
-		value = match value.get(name) {
-			Some(v) => v,
-			None => return None,
-		}
+/// Convert a toml value to a json value.
+/// Note: dates are kept as their string representation (e.g., "2022-10-06").
+pub fn toml_to_json(toml: Value) -> serde_json::Value {
+	match toml {
+		Value::String(v) => serde_json::Value::String(v),
+		Value::Integer(v) => serde_json::Value::from(v),
+		Value::Float(v) => serde_json::Value::from(v),
+		Value::Boolean(v) => serde_json::Value::Bool(v),
+		Value::Datetime(v) => serde_json::Value::String(v.to_string()),
+		Value::Array(v) => serde_json::Value::Array(v.into_iter().map(toml_to_json).collect()),
+		Value::Table(v) => serde_json::Value::Object(v.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect()),
 	}
-
-	Some(value)
 }
-// endregion: --- Old Utilities
+
+// endregion: --- Json
+
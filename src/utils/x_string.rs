@@ -1,6 +1,5 @@
-///! as_string  trait/implementations
-///! ----
-///
+//! as_string  trait/implementations
+//! ----
 use std::ffi::OsStr;
 use std::fs::DirEntry;
 use std::path::PathBuf;
@@ -109,7 +108,7 @@ impl XString for DirEntry {
 impl XString for Option<DirEntry> {
 	#[inline]
 	fn x_string(&self) -> Option<String> {
-		self.as_ref().and_then(|v| DirEntry::x_string(v))
+		self.as_ref().and_then(DirEntry::x_string)
 	}
 }
 // endregion: --- DirEntry
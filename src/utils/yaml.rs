@@ -0,0 +1,25 @@
+use crate::prelude::*;
+use serde_json::Value;
+use yaml_rust::Yaml;
+
+/// Convert a yaml value to a json value.
+/// Note: Only the string and integer keys are kept, and the aliases are null.
+pub fn yaml_to_json(yaml: Yaml) -> Value {
+	match yaml {
+		Yaml::String(v) => Value::String(v),
+		Yaml::Integer(v) => Value::from(v),
+		Yaml::Real(v) => v.parse::<f64>().map(Value::from).unwrap_or(Value::String(v)),
+		Yaml::Boolean(v) => Value::Bool(v),
+		Yaml::Array(v) => Value::Array(v.into_iter().map(yaml_to_json).collect()),
+		Yaml::Hash(v) => Value::Object(
+			v.into_iter()
+				.filter_map(|(k, v)| match k {
+					Yaml::String(k) => Some((k, yaml_to_json(v))),
+					Yaml::Integer(k) => Some((s!(k), yaml_to_json(v))),
+					_ => None,
+				})
+				.collect(),
+		),
+		Yaml::Null | Yaml::Alias(_) | Yaml::BadValue => Value::Null,
+	}
+}